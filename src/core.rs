@@ -1,22 +1,36 @@
+pub mod clock;
+pub mod config;
+pub mod hint;
 pub mod moves;
+pub mod score;
 pub mod table;
 
 use std::{
     cell::RefCell,
+    collections::{HashSet, VecDeque},
     fmt::{self, Display},
     rc::Rc,
+    time::{Duration, Instant},
 };
 
-use strum::IntoEnumIterator;
-
 use crate::{
-    core::{moves::Move, table::Table},
-    deck::card::Suit,
-    pile::Pile,
+    core::{
+        clock::Clock,
+        config::GameConfig,
+        moves::Move,
+        score::{Bankroll, Scoring},
+        table::Table,
+    },
+    deck::{DECK_SIZE, Deck},
+    pile::{Pile, PileType},
+    solver::{Solution, Solver},
+    variant::{Klondike, Variant},
 };
 
 type PileRef = Rc<RefCell<Pile>>;
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+/// The number of positions explored before giving up on finding a stalemate.
+const STALEMATE_POSITIONS: usize = 200;
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 #[derive(Debug)]
 pub enum Error {
@@ -24,10 +38,15 @@ pub enum Error {
     NoCardsMoved,
     InvalidMove,
     EmptyPile,
+    EmptyLane,
+    TooManyCards,
+    InvalidDealCode,
+    NoPassesLeft,
     Quit,
     Win,
     Help,
     History,
+    NoHint,
 }
 
 impl std::error::Error for Error {}
@@ -41,52 +60,201 @@ impl Display for Error {
             Error::NoCardsMoved => write!(f, "No cards were moved"),
             Error::InvalidMove => write!(f, "Invalid move, try again"),
             Error::EmptyPile => write!(f, "The pile is empty"),
+            Error::EmptyLane => write!(f, "Fill the empty lanes first"),
+            Error::TooManyCards => write!(f, "Not enough free space to move that many cards"),
+            Error::InvalidDealCode => write!(f, "Invalid deal code"),
+            Error::NoPassesLeft => write!(f, "No passes through the stock left"),
             Error::Quit => write!(f, "Exiting the game"),
             Error::Win => write!(f, "Successfully autofinshed"),
             Error::Help => write!(f, "Help message"),
             Error::History => write!(f, "Move history"),
+            Error::NoHint => write!(f, "No moves to suggest"),
         }
     }
 }
+/// Where a game stands after the last move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    Playing,
+    Won,
+    /// No move gets any closer to a win: the only ones left cycle the stock
+    /// or move cards back and forth. The player can only undo, restart the
+    /// deal or start a new one.
+    Stalemate,
+}
+
 struct HistoryItem {
     table: Table,
+    score: i32,
     move_played: Move,
 }
 
 impl HistoryItem {
-    fn new(table: &Table, move_played: Move) -> Self {
+    fn new(table: &Table, score: i32, move_played: Move) -> Self {
         let table = table.clone();
-        HistoryItem { table, move_played }
+        HistoryItem {
+            table,
+            score,
+            move_played,
+        }
     }
 }
 
-pub struct Game {
+pub struct Game<V: Variant = Klondike> {
+    variant: V,
     table: Table,
     moves: u32,
+    score: i32,
+    clock: Clock,
+    bankroll: Option<Bankroll>,
+    seed: Option<u64>,
+    deal_code: Option<String>,
     history: Vec<HistoryItem>,
+    hint: Option<Move>,
+    hints_shown: usize,
+    winnable: Option<bool>,
+    /// The time allowed to find a winnable deal, for games that only get
+    /// winnable deals.
+    winnable_search: Option<Duration>,
 }
 
-impl Game {
-    pub fn new() -> Game {
-        let table = Table::new();
+impl<V: Variant> Game<V> {
+    pub fn new(variant: V, config: GameConfig) -> Game<V> {
+        Game::from_seed(variant, rand::random(), config)
+    }
+
+    pub fn from_seed(variant: V, seed: u64, config: GameConfig) -> Game<V> {
+        let deck = variant.deck(seed);
+        let mut game = Game::from_deck(variant, deck, config);
+        game.seed = Some(seed);
+        game
+    }
+
+    /// Deal codes only describe a single standard deck, so variants using a
+    /// different deck cannot be started from one.
+    pub fn from_code(variant: V, code: &str, config: GameConfig) -> Result<Game<V>> {
+        if variant.deck(0).len() != DECK_SIZE {
+            return Err(Box::new(Error::InvalidDealCode));
+        }
+        let deck = Deck::from_code(code).ok_or(Error::InvalidDealCode)?;
+        Ok(Game::from_deck(variant, deck, config))
+    }
+
+    fn from_deck(variant: V, deck: Deck, config: GameConfig) -> Game<V> {
+        let config = variant.config(config);
+        let deal_code = deck.code();
+        let table = variant.deal(deck, config);
         Game {
-            table,
+            variant,
             moves: 0,
+            score: config.scoring().initial(&table),
+            table,
+            clock: Clock::new(),
+            bankroll: None,
+            seed: None,
+            deal_code,
             history: vec![],
+            hint: None,
+            hints_shown: 0,
+            winnable: None,
+            winnable_search: None,
         }
     }
 
+    pub fn with_bankroll(mut self, bankroll: Bankroll) -> Game<V> {
+        self.bankroll = Some(bankroll);
+        self
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn deal_code(&self) -> Option<&str> {
+        self.deal_code.as_deref()
+    }
+
+    /// Whether the solver confirmed that the deal can be won, if it was asked.
+    pub fn winnable(&self) -> Option<bool> {
+        self.winnable
+    }
+
+    /// The move suggested by the last hint, until another move is played.
+    pub fn hint(&self) -> Option<Move> {
+        self.hint
+    }
+
+    pub fn variant(&self) -> &V {
+        &self.variant
+    }
+
     pub fn table(&self) -> &Table {
         &self.table
     }
 
+    pub fn moves(&self) -> u32 {
+        self.moves
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    pub fn pause_clock(&mut self) {
+        self.clock.pause();
+    }
+
+    pub fn resume_clock(&mut self) {
+        self.clock.resume();
+    }
+
+    pub fn time_bonus(&self) -> i32 {
+        self.scoring().time_bonus(self.clock.elapsed())
+    }
+
+    /// The score including the time bonus, awarded once the game is won.
+    pub fn final_score(&self) -> i32 {
+        self.score + self.time_bonus()
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        self.table.config()
+    }
+
+    fn scoring(&self) -> Scoring {
+        self.config().scoring()
+    }
+
+    /// The bankroll balance including the current game, if one is kept.
+    pub fn balance(&self) -> Option<i64> {
+        self.bankroll
+            .as_ref()
+            .map(|bankroll| bankroll.balance() + self.score as i64)
+    }
+
+    pub fn save_bankroll(&self) -> std::io::Result<()> {
+        match &self.bankroll {
+            Some(bankroll) => bankroll.save(self.score),
+            None => Ok(()),
+        }
+    }
+
     pub fn play(&mut self, game_move: Move) -> Result<()> {
-        self.history.push(HistoryItem::new(&self.table, game_move));
+        if let Move::Hint = game_move {
+            return self.next_hint();
+        }
+        self.hint = None;
+        self.history
+            .push(HistoryItem::new(&self.table, self.score, game_move));
         let move_result = match game_move {
             Move::AutoFinish => self.auto_finish(),
-            Move::DrawCard => self.table.draw_card(),
-            Move::AutoMove(from, to) => self.table.auto_move(from, to),
-            Move::MoveCards(n, from, to) => self.table.move_cards(n, from, to),
+            Move::DrawCard | Move::AutoMove(..) | Move::MoveCards(..) | Move::RemovePair(..) => {
+                self.variant.play(&mut self.table, game_move)
+            }
             Move::Undo => {
                 self.undo();
                 Ok(())
@@ -95,11 +263,26 @@ impl Game {
             Move::Quit => Err(Box::new(Error::Quit) as Box<dyn std::error::Error>),
             Move::Invalid => Err(Box::new(Error::InvalidMove) as Box<dyn std::error::Error>),
             Move::History => Err(Box::new(Error::History) as Box<dyn std::error::Error>),
+            Move::Hint => unreachable!("hints are handled before the history"),
         };
         if move_result.is_ok() {
+            self.hints_shown = 0;
             match game_move {
                 Move::Undo | Move::History => (),
-                _ => self.moves += 1,
+                _ => {
+                    self.clock.start();
+                    self.moves += 1;
+                    let streak = self.streak();
+                    if let Some(item) = self.history.last() {
+                        self.score = self.scoring().apply(
+                            self.score,
+                            game_move,
+                            &item.table,
+                            &self.table,
+                            streak,
+                        );
+                    }
+                }
             }
         } else {
             self.history.pop();
@@ -107,6 +290,32 @@ impl Game {
         move_result
     }
 
+    /// Suggests the best move, or the next best one on each repeated request.
+    fn next_hint(&mut self) -> Result<()> {
+        let hints = hint::hints(&self.variant, &self.table);
+        if hints.is_empty() {
+            return Err(Box::new(Error::NoHint));
+        }
+        self.hint = Some(hints[self.hints_shown % hints.len()]);
+        self.hints_shown += 1;
+        Ok(())
+    }
+
+    /// The number of cards played to the discard pile in a row, up to the
+    /// last move.
+    fn streak(&self) -> u32 {
+        self.history
+            .iter()
+            .rev()
+            .take_while(|item| {
+                matches!(
+                    item.move_played,
+                    Move::AutoMove(_, PileType::Discard) | Move::MoveCards(_, _, PileType::Discard)
+                )
+            })
+            .count() as u32
+    }
+
     fn undo(&mut self) {
         match self.moves {
             0 => {
@@ -116,6 +325,7 @@ impl Game {
                 self.history.pop();
                 if let Some(t) = self.history.pop() {
                     self.table = t.table;
+                    self.score = t.score;
                     self.moves -= 1
                 };
             }
@@ -123,6 +333,9 @@ impl Game {
     }
 
     pub fn print_history(&self) {
+        if let Some(code) = &self.deal_code {
+            println!("Deal: {}\n", code);
+        }
         if self.history.is_empty() {
             println!("No moves");
             return;
@@ -137,15 +350,12 @@ impl Game {
     }
 
     fn auto_finish(&mut self) -> Result<()> {
-        let lanes_uncovered = (0..7).all(|i| {
-            let lane = self.table().lane(i);
-            lane.cards().iter().all(|card| !card.is_covered())
-        });
-        let piles_empty =
-            self.table().draw_pile().is_empty() && self.table().uncovered_pile().is_empty();
-        if lanes_uncovered && piles_empty {
-            let moves: u32 = (0..7).map(|i| self.table().lane(i).length() as u32).sum();
+        if self.variant.can_auto_finish(&self.table) {
+            let moves: u32 = (0..self.table.lane_count())
+                .map(|i| self.table().lane(i).length() as u32)
+                .sum();
             self.moves += moves;
+            self.score += self.scoring().auto_finish(moves);
             Err(Box::new(Error::Win))
         } else {
             Err(Box::new(Error::NoAutoFinish))
@@ -153,20 +363,204 @@ impl Game {
     }
 
     pub fn is_over(&self) -> bool {
-        Suit::iter()
-            .map(|suit| self.table().suit_pile(suit))
-            .all(|pile| pile.length() == 13)
+        self.variant.is_won(&self.table)
+    }
+
+    pub fn state(&self) -> GameState {
+        if self.is_over() {
+            GameState::Won
+        } else if self.is_stalemate() {
+            GameState::Stalemate
+        } else {
+            GameState::Playing
+        }
+    }
+
+    /// Whether no progress can be made: no position reachable from the
+    /// table, counting those that only differ by cycling the stock as the
+    /// same, is closer to a win. Positions are explored up to
+    /// [`STALEMATE_POSITIONS`], beyond which the game is not reported stuck.
+    fn is_stalemate(&self) -> bool {
+        let mut seen = HashSet::from([position_key(&self.table)]);
+        let mut queue = VecDeque::from([self.table.clone()]);
+        while let Some(table) = queue.pop_front() {
+            for drawn in self.stock_cycle(table) {
+                for game_move in self.variant.legal_moves(&drawn) {
+                    if matches!(game_move, Move::DrawCard) {
+                        continue;
+                    }
+                    let mut after = drawn.clone();
+                    if self.variant.play(&mut after, game_move).is_err() {
+                        continue;
+                    }
+                    if hint::is_progress(&self.table, &after) {
+                        return false;
+                    }
+                    if seen.insert(position_key(&after)) {
+                        if seen.len() > STALEMATE_POSITIONS {
+                            return false;
+                        }
+                        queue.push_back(after);
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// The table followed by the positions reached by drawing from it, until
+    /// the stock and waste are back where they started or drawing fails.
+    fn stock_cycle(&self, table: Table) -> Vec<Table> {
+        let draw_count = table.config().draw_count();
+        let stock = table.draw_pile().cards();
+        let waste = table.uncovered_pile().cards();
+        let cards = stock.len() + waste.len();
+        let draws = stock.len().div_ceil(draw_count) + cards.div_ceil(draw_count) + 2;
+        let mut tables = vec![table];
+        for _ in 0..draws {
+            let mut next = tables[tables.len() - 1].clone();
+            if self.variant.play(&mut next, Move::DrawCard).is_err()
+                || (next.draw_pile().cards() == stock && next.uncovered_pile().cards() == waste)
+            {
+                break;
+            }
+            tables.push(next);
+        }
+        tables
+    }
+
+    /// Plays the same deal again from the start.
+    pub fn restart(&mut self) {
+        if let Some(first) = self.history.first() {
+            let table = first.table.clone();
+            self.reset(table);
+        }
+    }
+
+    /// Starts a new random deal with the same rules, which the solver
+    /// confirms can be won if the game was started with
+    /// [`Game::new_winnable`].
+    pub fn redeal(&mut self) {
+        let (seed, winnable) = match self.winnable_search {
+            Some(time_limit) => {
+                let (seed, winnable) = winnable_seed(*self.config(), time_limit);
+                (seed, Some(winnable))
+            }
+            None => (rand::random(), None),
+        };
+        let deck = self.variant.deck(seed);
+        self.deal_code = deck.code();
+        let table = self.variant.deal(deck, *self.config());
+        self.reset(table);
+        self.seed = Some(seed);
+        self.winnable = winnable;
+    }
+
+    /// Replaces the table, settling the score of the game given up in the
+    /// bankroll.
+    fn reset(&mut self, table: Table) {
+        if let Some(bankroll) = &mut self.bankroll {
+            bankroll.add(self.score);
+        }
+        self.score = self.scoring().initial(&table);
+        self.table = table;
+        self.moves = 0;
+        self.clock = Clock::new();
+        self.history.clear();
+        self.hint = None;
+        self.hints_shown = 0;
+    }
+}
+
+/// Identifies a position regardless of the order of the stock and waste.
+fn position_key(table: &Table) -> String {
+    let mut stock = table
+        .draw_pile()
+        .cards()
+        .into_iter()
+        .chain(table.uncovered_pile().cards())
+        .map(|card| (card.suit() as u8, card.rank() as u8))
+        .collect::<Vec<(u8, u8)>>();
+    stock.sort();
+    let lanes = (0..table.lane_count()).map(|i| format!("{:?}", table.lane(i).cards()));
+    let cells = (0..table.cell_count()).map(|i| format!("{:?}", table.cell(i).cards()));
+    let foundations =
+        (0..table.foundation_count()).map(|i| format!("{:?}", table.foundation(i).cards()));
+    let reserve = format!("{:?}", table.reserve().cards());
+    let piles = lanes
+        .chain(cells)
+        .chain(foundations)
+        .chain([reserve])
+        .collect::<Vec<String>>();
+    format!("{:?}{}", stock, piles.join("|"))
+}
+
+impl Game<Klondike> {
+    /// Keeps dealing until the solver confirms that a deal can be won, within
+    /// `time_limit`. Falls back to the last random deal when none is found in
+    /// time.
+    /// New deals after this one are also searched for a win.
+    pub fn new_winnable(config: GameConfig, time_limit: Duration) -> Game {
+        let config = Klondike.config(config);
+        let (seed, winnable) = winnable_seed(config, time_limit);
+        let mut game = Game::from_seed(Klondike, seed, config);
+        game.winnable = Some(winnable);
+        game.winnable_search = Some(time_limit);
+        game
+    }
+}
+
+/// Deals Klondike tables from random seeds until the solver confirms one can
+/// be won within `time_limit`, or returns the last seed tried.
+fn winnable_seed(config: GameConfig, time_limit: Duration) -> (u64, bool) {
+    let deadline = Instant::now() + time_limit;
+    loop {
+        let seed = rand::random();
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let solution = Solver::new()
+            .time_limit(remaining)
+            .solve(&Table::new(seed, config));
+        let winnable = matches!(solution, Solution::Win { .. });
+        if winnable || Instant::now() >= deadline {
+            return (seed, winnable);
+        }
     }
 }
 
 impl Default for Game {
     fn default() -> Self {
-        Game::new()
+        Game::new(Klondike, GameConfig::default())
     }
 }
 
-impl Display for Game {
+impl<V: Variant> Display for Game<V> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Moves: {}", self.moves)
+        write!(f, "{}\t", self.variant.name())?;
+        let currency = match self.scoring() {
+            Scoring::Vegas => "$",
+            _ => "",
+        };
+        write!(
+            f,
+            "Moves: {}\tTime: {}\tScore: {}{}",
+            self.moves, self.clock, currency, self.score
+        )?;
+        if let Some(balance) = self.balance() {
+            write!(f, "\tBankroll: ${}", balance)?;
+        }
+        write!(f, "\tPass: {}", self.table.pass())?;
+        if let Some(max_passes) = self.config().max_passes() {
+            write!(f, "/{}", max_passes)?;
+        }
+        match self.winnable {
+            Some(true) => write!(f, "\tWinnable deal")?,
+            Some(false) => write!(f, "\tDeal may not be winnable")?,
+            None => (),
+        }
+        match (self.seed, &self.deal_code) {
+            (Some(seed), _) => write!(f, "\tSeed: {}", seed),
+            (None, Some(code)) => write!(f, "\tDeal: {}", code),
+            (None, None) => Ok(()),
+        }
     }
 }
@@ -0,0 +1,52 @@
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+/// Elapsed playing time. The clock starts on the first move and does not
+/// count the time spent while paused.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Clock {
+    running_since: Option<Instant>,
+    elapsed: Duration,
+    started: bool,
+}
+
+impl Clock {
+    pub fn new() -> Clock {
+        Clock::default()
+    }
+
+    pub fn start(&mut self) {
+        if !self.started {
+            self.started = true;
+            self.running_since = Some(Instant::now());
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.elapsed += since.elapsed();
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.started && self.running_since.is_none() {
+            self.running_since = Some(Instant::now());
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        match self.running_since {
+            Some(since) => self.elapsed + since.elapsed(),
+            None => self.elapsed,
+        }
+    }
+}
+
+impl Display for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.elapsed().as_secs();
+        write!(f, "{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt::{self, Display};
+
+use crate::{core::score::Scoring, deck::card::Rank};
+
+const MAX_LANES: usize = 13;
+
+/// Which cards can be placed on an empty lane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyLane {
+    Kings,
+    Any,
+    /// Empty lanes stay empty.
+    None,
+}
+
+/// How cards are built down on the lanes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Build {
+    AlternateColours,
+    SameSuit,
+    AnySuit,
+}
+
+/// The rules of a game. Use [`GameConfig::builder`] to change the defaults,
+/// which are those of classic Klondike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameConfig {
+    lanes: usize,
+    empty_lane: EmptyLane,
+    build_rule: Build,
+    draw_count: usize,
+    max_passes: Option<u32>,
+    wrap: bool,
+    base_rank: Rank,
+    scoring: Scoring,
+}
+
+impl GameConfig {
+    pub fn builder() -> GameConfigBuilder {
+        GameConfigBuilder::default()
+    }
+
+    /// A builder starting from these rules, so that some of them can be changed.
+    pub fn to_builder(&self) -> GameConfigBuilder {
+        GameConfigBuilder {
+            lanes: Some(self.lanes),
+            empty_lane: Some(self.empty_lane),
+            build_rule: Some(self.build_rule),
+            draw_count: Some(self.draw_count),
+            max_passes: Some(self.max_passes),
+            wrap: Some(self.wrap),
+            base_rank: Some(self.base_rank),
+            scoring: Some(self.scoring),
+        }
+    }
+
+    pub fn lanes(&self) -> usize {
+        self.lanes
+    }
+
+    pub fn empty_lane(&self) -> EmptyLane {
+        self.empty_lane
+    }
+
+    pub fn build_rule(&self) -> Build {
+        self.build_rule
+    }
+
+    pub fn draw_count(&self) -> usize {
+        self.draw_count
+    }
+
+    pub fn max_passes(&self) -> Option<u32> {
+        self.max_passes
+    }
+
+    /// Whether ranks wrap around, so that an ace and a king are adjacent.
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    /// The rank the suit piles start from.
+    pub fn base_rank(&self) -> Rank {
+        self.base_rank
+    }
+
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig::builder().build()
+    }
+}
+
+impl Display for GameConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let empty_lane = match self.empty_lane {
+            EmptyLane::Kings => "kings only",
+            EmptyLane::Any => "any card",
+            EmptyLane::None => "no card",
+        };
+        let passes = match self.max_passes {
+            Some(1) => "1 pass".to_string(),
+            Some(n) => format!("{n} passes"),
+            None => "unlimited passes".to_string(),
+        };
+        let build = match self.build_rule {
+            Build::AlternateColours => "alternate colours",
+            Build::SameSuit => "suit",
+            Build::AnySuit => "any suit",
+        };
+        let scoring = match self.scoring {
+            Scoring::Standard => "standard",
+            Scoring::Vegas => "Vegas",
+            Scoring::CardsLeft => "cards left",
+            Scoring::Streak => "streak",
+        };
+        let wrap = if self.wrap { ", ranks wrap around" } else { "" };
+        let base_rank = match self.base_rank {
+            Rank::Ace => String::new(),
+            rank => format!(", suit piles start from {}", rank.to_string().trim()),
+        };
+        write!(
+            f,
+            "Rules: {} lanes building down in {}, draw {}, {}, empty lanes take {}{}{}, {} scoring",
+            self.lanes, build, self.draw_count, passes, empty_lane, wrap, base_rank, scoring
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GameConfigBuilder {
+    lanes: Option<usize>,
+    empty_lane: Option<EmptyLane>,
+    build_rule: Option<Build>,
+    draw_count: Option<usize>,
+    max_passes: Option<Option<u32>>,
+    wrap: Option<bool>,
+    base_rank: Option<Rank>,
+    scoring: Option<Scoring>,
+}
+
+impl GameConfigBuilder {
+    /// The number of lanes, between 1 and 13.
+    pub fn lanes(mut self, lanes: usize) -> Self {
+        self.lanes = Some(lanes.clamp(1, MAX_LANES));
+        self
+    }
+
+    pub fn empty_lane(mut self, empty_lane: EmptyLane) -> Self {
+        self.empty_lane = Some(empty_lane);
+        self
+    }
+
+    pub fn build_rule(mut self, build_rule: Build) -> Self {
+        self.build_rule = Some(build_rule);
+        self
+    }
+
+    pub fn draw_count(mut self, draw_count: usize) -> Self {
+        self.draw_count = Some(draw_count.max(1));
+        self
+    }
+
+    /// `None` allows unlimited passes through the stock.
+    pub fn max_passes(mut self, max_passes: Option<u32>) -> Self {
+        self.max_passes = Some(max_passes.map(|passes| passes.max(1)));
+        self
+    }
+
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = Some(wrap);
+        self
+    }
+
+    pub fn base_rank(mut self, base_rank: Rank) -> Self {
+        self.base_rank = Some(base_rank);
+        self
+    }
+
+    pub fn scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = Some(scoring);
+        self
+    }
+
+    /// Unless set explicitly, Vegas scoring limits the stock to a single pass
+    /// when drawing one card at a time, or three passes when drawing three.
+    pub fn build(self) -> GameConfig {
+        let draw_count = self.draw_count.unwrap_or(1);
+        let scoring = self.scoring.unwrap_or(Scoring::Standard);
+        let max_passes = self.max_passes.unwrap_or(match (scoring, draw_count) {
+            (Scoring::Vegas, 1) => Some(1),
+            (Scoring::Vegas, _) => Some(3),
+            _ => None,
+        });
+        GameConfig {
+            lanes: self.lanes.unwrap_or(7),
+            empty_lane: self.empty_lane.unwrap_or(EmptyLane::Kings),
+            build_rule: self.build_rule.unwrap_or(Build::AlternateColours),
+            draw_count,
+            max_passes,
+            wrap: self.wrap.unwrap_or(false),
+            base_rank: self.base_rank.unwrap_or(Rank::Ace),
+            scoring,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    core::{config::EmptyLane, moves::Move, position_key, table::Table},
+    deck::card::Rank,
+    pile::{Pile, PileType},
+    variant::Variant,
+};
+
+const CARD_UNCOVERED: i32 = 50;
+const CARD_TO_SUIT_PILE: i32 = 30;
+const LANE_EMPTIED: i32 = 20;
+const FROM_WASTE: i32 = 10;
+const DRAW: i32 = 1;
+const NO_PROGRESS: i32 = -2;
+const TO_CELL: i32 = -5;
+
+/// The legal moves of the table, best first. Moves that turn cards face up
+/// come first, then those sending cards to the suit piles, emptying a lane
+/// for a king, or playing from the waste. Drawing comes after those, and
+/// moves that change nothing but the order of the lanes come last. Only
+/// moves that can be played by typing their keys are kept.
+pub fn hints<V: Variant>(variant: &V, table: &Table) -> Vec<Move> {
+    let mut hints = variant
+        .legal_moves(table)
+        .into_iter()
+        .filter(|&game_move| replays(variant, table, game_move))
+        .map(|game_move| {
+            let mut after = table.clone();
+            let _ = variant.play(&mut after, game_move);
+            (rank(game_move, table, &after), game_move)
+        })
+        .collect::<Vec<(i32, Move)>>();
+    hints.sort_by_key(|(rank, _)| -rank);
+    hints.into_iter().map(|(_, game_move)| game_move).collect()
+}
+
+/// Whether typing the keys of `game_move` plays that same move. Naming two
+/// piles plays [`Variant::pile_move`], which may move another number of
+/// cards than the hint did.
+fn replays<V: Variant>(variant: &V, table: &Table, game_move: Move) -> bool {
+    let (from, to) = match game_move {
+        Move::MoveCards(_, from, to) => (from, to),
+        _ => return true,
+    };
+    if variant.pile_key(from).is_none() || variant.pile_key(to).is_none() {
+        return true;
+    }
+    let (mut played, mut typed) = (table.clone(), table.clone());
+    variant.play(&mut played, game_move).is_ok()
+        && variant
+            .play(&mut typed, variant.pile_move(from, to))
+            .is_ok()
+        && position_key(&played) == position_key(&typed)
+}
+
+fn rank(game_move: Move, before: &Table, after: &Table) -> i32 {
+    let uncovered = before.covered_cards() as i32 - after.covered_cards() as i32;
+    let sent = after.suit_cards() as i32 - before.suit_cards() as i32;
+    let emptied = after.empty_lanes() > before.empty_lanes();
+    let mut rank = uncovered * CARD_UNCOVERED + sent * CARD_TO_SUIT_PILE;
+    if emptied && lane_usable(after) {
+        rank += LANE_EMPTIED;
+    }
+    rank += match game_move {
+        Move::DrawCard => DRAW,
+        Move::MoveCards(_, PileType::Uncovered | PileType::Reserve, _) => FROM_WASTE,
+        Move::MoveCards(_, _, PileType::Discard) => FROM_WASTE,
+        Move::MoveCards(_, _, PileType::Cell(_)) => TO_CELL,
+        Move::MoveCards(_, PileType::Lane(_), PileType::Lane(_)) if uncovered == 0 && !emptied => {
+            NO_PROGRESS
+        }
+        _ => 0,
+    };
+    rank
+}
+
+/// Whether `after` is closer to a win than `before`: more cards are face up
+/// or on the suit piles, fewer are left in the lanes, cells and reserve, or
+/// more lanes are empty and can be used.
+pub fn is_progress(before: &Table, after: &Table) -> bool {
+    after.covered_cards() < before.covered_cards()
+        || after.suit_cards() > before.suit_cards()
+        || cards_in_play(after) < cards_in_play(before)
+        || (after.empty_lanes() > before.empty_lanes() && lane_usable(after))
+}
+
+fn cards_in_play(table: &Table) -> usize {
+    let lanes = (0..table.lane_count()).map(|i| table.lane(i).length());
+    let cells = (0..table.cell_count()).map(|i| table.cell(i).length());
+    lanes.chain(cells).sum::<usize>() + table.reserve().length()
+}
+
+/// Whether something could be moved to an empty lane.
+fn lane_usable(table: &Table) -> bool {
+    match table.config().empty_lane() {
+        EmptyLane::Any => true,
+        EmptyLane::Kings => king_available(table),
+        EmptyLane::None => false,
+    }
+}
+
+/// Whether a king could be moved to an empty lane: it has to be face up and
+/// not already at the bottom of a lane.
+fn king_available(table: &Table) -> bool {
+    let is_king = |pile: &Pile| {
+        pile.top_card()
+            .is_some_and(|card| card.rank() == Rank::King)
+    };
+    let in_lanes = (0..table.lane_count()).any(|i| {
+        let cards = table.lane(i).cards();
+        cards
+            .iter()
+            .skip(1)
+            .any(|card| !card.is_covered() && card.rank() == Rank::King)
+    });
+    let in_cells = (0..table.cell_count()).any(|i| is_king(&table.cell(i)));
+    in_lanes || in_cells || is_king(&table.uncovered_pile()) || is_king(&table.reserve())
+}
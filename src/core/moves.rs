@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
-use crate::deck::card::Suit;
 use crate::pile::PileType;
+use crate::variant::{Klondike, Variant};
 
 #[derive(Debug, Clone, Copy)]
 pub enum Move {
@@ -9,55 +9,46 @@ pub enum Move {
     DrawCard,
     AutoMove(PileType, PileType),
     MoveCards(usize, PileType, PileType),
+    /// Removes the top cards of two piles at once. Naming the same pile twice
+    /// removes its top card alone.
+    RemovePair(PileType, PileType),
     History,
     Help,
+    Hint,
     Undo,
     Quit,
     Invalid,
 }
 impl Move {
-    fn parse_piles(piles: &str) -> Vec<Option<PileType>> {
-        piles
-            .chars()
-            .map(|pile| match pile {
-                'P' => Some(PileType::Uncovered),
-                'C' => Some(PileType::Suit(Suit::Clubs)),
-                'D' => Some(PileType::Suit(Suit::Diamonds)),
-                'H' => Some(PileType::Suit(Suit::Hearts)),
-                'S' => Some(PileType::Suit(Suit::Spades)),
-                c if c.is_numeric() && (1..=7).contains(&c.to_digit(10).unwrap()) => {
-                    Some(PileType::Lane(c.to_digit(10).unwrap() as usize))
-                }
-                _ => None,
-            })
-            .collect()
+    fn parse_piles<V: Variant>(piles: &str, variant: &V) -> Vec<Option<PileType>> {
+        piles.chars().map(|pile| variant.parse_pile(pile)).collect()
     }
-}
 
-impl From<String> for Move {
-    fn from(item: String) -> Move {
+    /// Parses a move using the pile keys of the given variant.
+    pub fn parse<V: Variant>(item: &str, variant: &V) -> Move {
         let item = item.trim().to_uppercase();
         match item.len() {
             1 => match &item[..] {
                 "A" => Move::AutoFinish,
                 "N" => Move::DrawCard,
                 "?" => Move::Help,
+                "I" => Move::Hint,
                 "L" => Move::History,
                 "Q" => Move::Quit,
                 "U" => Move::Undo,
                 _ => Move::Invalid,
             },
             2 => {
-                let piles = Move::parse_piles(&item[..]);
+                let piles = Move::parse_piles(&item[..], variant);
                 match &piles[..] {
-                    [Some(a), Some(b)] => Move::AutoMove(*a, *b),
+                    [Some(a), Some(b)] => variant.pile_move(*a, *b),
                     _ => Move::Invalid,
                 }
             }
             3 | 4 => {
                 let (piles, number) = (&item[..2], &item[2..]);
                 let n: usize = number.parse().unwrap_or(0);
-                let ps = Move::parse_piles(piles);
+                let ps = Move::parse_piles(piles, variant);
                 match &ps[..] {
                     [Some(a), Some(b)] => Move::MoveCards(n, *a, *b),
                     _ => Move::Invalid,
@@ -68,6 +59,12 @@ impl From<String> for Move {
     }
 }
 
+impl From<String> for Move {
+    fn from(item: String) -> Move {
+        Move::parse(&item, &Klondike)
+    }
+}
+
 impl Display for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let m = match self {
@@ -75,11 +72,13 @@ impl Display for Move {
             Move::DrawCard => "N",
             Move::AutoMove(from, to) => &format!("{}{}", from, to)[..],
             Move::MoveCards(n, from, to) => &format!("{}{}{}", from, to, n)[..],
+            Move::RemovePair(a, b) => &format!("{}{}", a, b)[..],
             Move::History => "L",
             Move::Help => "?",
+            Move::Hint => "I",
             Move::Undo => "U",
             Move::Quit => "Q",
-            Move::Invalid => "I",
+            Move::Invalid => "Invalid",
         };
         write!(f, "{}", m)
     }
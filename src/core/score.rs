@@ -0,0 +1,163 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{core::moves::Move, core::table::Table, pile::PileType};
+
+const TO_SUIT_PILE: i32 = 10;
+const WASTE_TO_LANE: i32 = 5;
+const CARD_TURNED: i32 = 5;
+const SUIT_PILE_TO_LANE: i32 = -15;
+const RECYCLE_DRAW_ONE: i32 = -100;
+const RECYCLE_DRAW_THREE: i32 = -20;
+
+const TIME_BONUS: u64 = 700_000;
+const TIME_BONUS_MIN_SECONDS: u64 = 30;
+
+const VEGAS_ANTE: i32 = -52;
+const VEGAS_SUIT_CARD: i32 = 5;
+
+const BANKROLL_FILE: &str = ".solitaire_bankroll";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scoring {
+    Standard,
+    Vegas,
+    /// Golf scoring: the cards left in the lanes, or minus the cards left in
+    /// the stock once the lanes are cleared. Lower is better.
+    CardsLeft,
+    /// TriPeaks scoring: each card played to the discard pile scores one point
+    /// more than the previous one, until a card is drawn from the stock.
+    Streak,
+}
+
+impl Scoring {
+    /// The score a game starts with on the dealt table.
+    pub fn initial(&self, table: &Table) -> i32 {
+        match self {
+            Scoring::Standard => 0,
+            Scoring::Vegas => VEGAS_ANTE,
+            Scoring::CardsLeft => cards_left(table),
+            Scoring::Streak => 0,
+        }
+    }
+
+    /// The new score after a move that turned `before` into `after`. `streak`
+    /// counts the cards played to the discard pile in a row, this move included.
+    pub fn apply(
+        &self,
+        score: i32,
+        game_move: Move,
+        before: &Table,
+        after: &Table,
+        streak: u32,
+    ) -> i32 {
+        match self {
+            Scoring::Standard => (score + standard(game_move, before, after)).max(0),
+            Scoring::Vegas => {
+                let sent = after.suit_cards() as i32 - before.suit_cards() as i32;
+                score + sent * VEGAS_SUIT_CARD
+            }
+            Scoring::CardsLeft => cards_left(after),
+            Scoring::Streak => score + streak as i32,
+        }
+    }
+
+    /// Bonus added to the score of a won game, the faster the higher.
+    /// Games shorter than 30 seconds and Vegas games get no bonus.
+    pub fn time_bonus(&self, elapsed: Duration) -> i32 {
+        let seconds = elapsed.as_secs();
+        match self {
+            Scoring::Standard if seconds >= TIME_BONUS_MIN_SECONDS => (TIME_BONUS / seconds) as i32,
+            _ => 0,
+        }
+    }
+
+    /// Points for the cards sent to the suit piles when autofinishing.
+    pub fn auto_finish(&self, cards: u32) -> i32 {
+        match self {
+            Scoring::Standard => cards as i32 * TO_SUIT_PILE,
+            Scoring::Vegas => cards as i32 * VEGAS_SUIT_CARD,
+            Scoring::CardsLeft | Scoring::Streak => 0,
+        }
+    }
+}
+
+fn cards_left(table: &Table) -> i32 {
+    let lanes = (0..table.lane_count())
+        .map(|i| table.lane(i).length() as i32)
+        .sum();
+    match lanes {
+        0 => -(table.draw_pile().length() as i32),
+        n => n,
+    }
+}
+
+fn standard(game_move: Move, before: &Table, after: &Table) -> i32 {
+    let transfer = match game_move {
+        Move::AutoMove(from, to) | Move::MoveCards(_, from, to) => match (from, to) {
+            (PileType::Uncovered | PileType::Lane(_) | PileType::Reserve, PileType::Suit(_)) => {
+                TO_SUIT_PILE
+            }
+            (PileType::Uncovered, PileType::Lane(_)) => WASTE_TO_LANE,
+            (PileType::Suit(_), PileType::Lane(_)) => SUIT_PILE_TO_LANE,
+            _ => 0,
+        },
+        _ => 0,
+    };
+    let turned = before.covered_cards().saturating_sub(after.covered_cards()) as i32;
+    let recycles = after.pass().saturating_sub(before.pass()) as i32;
+    let recycle_penalty = match after.config().draw_count() {
+        1 => RECYCLE_DRAW_ONE,
+        _ => RECYCLE_DRAW_THREE,
+    };
+    transfer + turned * CARD_TURNED + recycles * recycle_penalty
+}
+
+/// The Vegas balance carried over between games, stored in a local file.
+#[derive(Debug, Clone)]
+pub struct Bankroll {
+    path: PathBuf,
+    balance: i64,
+}
+
+impl Bankroll {
+    /// Loads the bankroll from the user's home directory, or the current
+    /// directory if there is none. A missing file is an empty bankroll.
+    pub fn load() -> io::Result<Bankroll> {
+        let dir = env::var_os("HOME").map_or(PathBuf::from("."), PathBuf::from);
+        Bankroll::load_from(&dir.join(BANKROLL_FILE))
+    }
+
+    pub fn load_from(path: &Path) -> io::Result<Bankroll> {
+        let balance = match fs::read_to_string(path) {
+            Ok(s) => s
+                .trim()
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e),
+        };
+        Ok(Bankroll {
+            path: path.to_path_buf(),
+            balance,
+        })
+    }
+
+    /// The balance before the current game.
+    pub fn balance(&self) -> i64 {
+        self.balance
+    }
+
+    /// Settles the score of a game that is over, before starting another.
+    pub fn add(&mut self, score: i32) {
+        self.balance += score as i64;
+    }
+
+    /// Writes the balance including the score of the current game.
+    pub fn save(&self, score: i32) -> io::Result<()> {
+        fs::write(&self.path, format!("{}\n", self.balance + score as i64))
+    }
+}
@@ -1,52 +1,167 @@
-use super::{Error, PileRef, Result};
+use super::{Error, PileRef, Result, config::GameConfig, moves::Move};
 use crate::{
     deck::{
         Deck,
-        card::{self, Card, Suit},
+        card::{Card, Suit},
     },
     pile::{Pile, PileType},
 };
 use std::{
-    cell::{Ref, RefCell},
-    collections::HashMap,
+    cell::{Ref, RefCell, RefMut},
     rc::Rc,
 };
 use strum::IntoEnumIterator;
 #[derive(Debug)]
 pub struct Table {
-    suit_piles: HashMap<card::Suit, PileRef>,
+    foundations: Vec<PileRef>,
     lanes: Vec<PileRef>,
     draw_pile: PileRef,
     uncovered_pile: PileRef,
+    reserve: PileRef,
+    cells: Vec<PileRef>,
+    config: GameConfig,
+    pass: u32,
 }
 
 impl Table {
-    pub fn new() -> Table {
-        let mut deck = Deck::new();
-        let mut suit_piles = HashMap::new();
-        for suit in Suit::iter() {
-            let pile = Rc::new(RefCell::new(Pile::new(vec![], PileType::Suit(suit))));
-            suit_piles.insert(suit, pile);
-        }
-        let lanes: Vec<PileRef> = (1..=7)
-            .map(|i| Pile::new(deck.pick_cards(i), PileType::Lane(i as usize)))
-            .map(|mut lane| lane.flip_top_card())
-            .map(|lane| Rc::new(RefCell::new(lane)))
+    pub fn new(seed: u64, config: GameConfig) -> Table {
+        Table::from_deck(Deck::with_seed(seed), config)
+    }
+
+    pub fn from_code(code: &str, config: GameConfig) -> Result<Table> {
+        let deck = Deck::from_code(code).ok_or(Error::InvalidDealCode)?;
+        Ok(Table::from_deck(deck, config))
+    }
+
+    /// Deals the Klondike layout: lane `n` gets `n` cards, the rest go to the stock.
+    pub fn from_deck(mut deck: Deck, config: GameConfig) -> Table {
+        let lanes = (1..=config.lanes() as u8)
+            .map(|i| deck.pick_cards(i))
+            .collect();
+        Table::from_lanes(lanes, deck.pick_all_cards(), config)
+    }
+
+    /// Lays out the given lanes, numbered from 1, turning their top card face
+    /// up. The stock is dealt face down and the suit piles start empty.
+    pub fn from_lanes(lanes: Vec<Vec<Card>>, stock: Vec<Card>, config: GameConfig) -> Table {
+        let foundations = Table::foundations(&Suit::iter().collect::<Vec<Suit>>());
+        let lanes: Vec<PileRef> = lanes
+            .into_iter()
+            .enumerate()
+            .map(|(i, cards)| Pile::new(cards, PileType::Lane(i + 1)))
+            .map(|mut lane| {
+                if lane.top_card_is_covered() {
+                    lane.flip_top_card();
+                }
+                Rc::new(RefCell::new(lane))
+            })
             .collect();
         let uncovered_pile = Rc::new(RefCell::new(Pile::new(vec![], PileType::Uncovered)));
-        let draw_pile = Rc::new(RefCell::new(Pile::new(
-            deck.pick_all_cards(),
-            PileType::Draw,
-        )));
+        let draw_pile = Rc::new(RefCell::new(Pile::new(stock, PileType::Draw)));
+        let reserve = Rc::new(RefCell::new(Pile::new(vec![], PileType::Reserve)));
 
         Table {
-            suit_piles,
+            foundations,
             lanes,
             draw_pile,
             uncovered_pile,
+            reserve,
+            cells: vec![],
+            config,
+            pass: 1,
         }
     }
 
+    /// Turns the waste into a discard pile, which takes cards from the lanes.
+    pub fn with_discard(mut self) -> Table {
+        let cards = self.uncovered_pile.borrow_mut().remove_all_cards();
+        self.uncovered_pile = Rc::new(RefCell::new(Pile::new(cards, PileType::Discard)));
+        self
+    }
+
+    /// Replaces the suit piles with one empty foundation for each of the given
+    /// suits, as games with more than one deck need.
+    pub fn with_foundations(mut self, suits: &[Suit]) -> Table {
+        self.foundations = Table::foundations(suits);
+        self
+    }
+
+    fn foundations(suits: &[Suit]) -> Vec<PileRef> {
+        suits
+            .iter()
+            .map(|&suit| Rc::new(RefCell::new(Pile::new(vec![], PileType::Suit(suit)))))
+            .collect()
+    }
+
+    /// Sets aside the given cards as a reserve, with only the top one face up.
+    pub fn with_reserve(mut self, cards: Vec<Card>) -> Table {
+        let mut reserve = Pile::new(cards, PileType::Reserve);
+        if reserve.top_card_is_covered() {
+            reserve.flip_top_card();
+        }
+        self.reserve = Rc::new(RefCell::new(reserve));
+        self
+    }
+
+    /// Adds `count` empty free cells, numbered from 1.
+    pub fn with_cells(mut self, count: usize) -> Table {
+        self.cells = (1..=count)
+            .map(|i| Rc::new(RefCell::new(Pile::new(vec![], PileType::Cell(i)))))
+            .collect();
+        self
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn lane_count(&self) -> usize {
+        self.lanes.len()
+    }
+
+    pub fn foundation_count(&self) -> usize {
+        self.foundations.len()
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn empty_lanes(&self) -> usize {
+        self.lanes.iter().filter(|l| l.borrow().is_empty()).count()
+    }
+
+    pub fn empty_cells(&self) -> usize {
+        self.cells.iter().filter(|c| c.borrow().is_empty()).count()
+    }
+
+    /// The current pass through the stock, starting from 1.
+    pub fn pass(&self) -> u32 {
+        self.pass
+    }
+
+    /// The number of face-down cards left in the lanes.
+    pub fn covered_cards(&self) -> usize {
+        self.lanes
+            .iter()
+            .map(|lane| {
+                lane.borrow()
+                    .cards()
+                    .iter()
+                    .filter(|c| c.is_covered())
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The number of cards sent to the suit piles.
+    pub fn suit_cards(&self) -> usize {
+        self.foundations
+            .iter()
+            .map(|pile| pile.borrow().length())
+            .sum()
+    }
+
     pub fn draw_pile(&self) -> Ref<'_, Pile> {
         self.draw_pile.borrow()
     }
@@ -55,21 +170,72 @@ impl Table {
         self.uncovered_pile.borrow()
     }
 
+    pub fn reserve(&self) -> Ref<'_, Pile> {
+        self.reserve.borrow()
+    }
+
     pub fn lane(&self, index: usize) -> Ref<'_, Pile> {
         self.lanes[index].borrow()
     }
 
-    pub fn suit_pile(&self, suit: Suit) -> Ref<'_, Pile> {
-        self.suit_piles[&suit].borrow()
+    /// The suit piles in order, from the first of the clubs.
+    pub fn foundation(&self, index: usize) -> Ref<'_, Pile> {
+        self.foundations[index].borrow()
     }
 
-    fn get_pile(&self, pile_type: PileType) -> PileRef {
-        match pile_type {
-            PileType::Draw => PileRef::clone(&self.draw_pile),
-            PileType::Uncovered => PileRef::clone(&self.uncovered_pile),
-            PileType::Lane(i) => PileRef::clone(self.lanes.get(i - 1).unwrap()),
-            PileType::Suit(suit) => PileRef::clone(self.suit_piles.get(&suit).unwrap()),
-        }
+    pub fn cell(&self, index: usize) -> Ref<'_, Pile> {
+        self.cells[index].borrow()
+    }
+
+    pub fn lane_mut(&self, index: usize) -> RefMut<'_, Pile> {
+        self.lanes[index].borrow_mut()
+    }
+
+    fn get_pile(&self, pile_type: PileType) -> Result<PileRef> {
+        let pile = match pile_type {
+            PileType::Draw => &self.draw_pile,
+            PileType::Uncovered | PileType::Discard => &self.uncovered_pile,
+            PileType::Reserve => &self.reserve,
+            PileType::Lane(i) => i
+                .checked_sub(1)
+                .and_then(|i| self.lanes.get(i))
+                .ok_or(Error::InvalidMove)?,
+            PileType::Suit(suit) => self
+                .foundations_of(suit)
+                .max_by_key(|pile| pile.borrow().length())
+                .ok_or(Error::InvalidMove)?,
+            PileType::Cell(i) => i
+                .checked_sub(1)
+                .and_then(|i| self.cells.get(i))
+                .ok_or(Error::InvalidMove)?,
+        };
+        Ok(PileRef::clone(pile))
+    }
+
+    fn foundations_of(&self, suit: Suit) -> impl DoubleEndedIterator<Item = &PileRef> {
+        self.foundations
+            .iter()
+            .filter(move |pile| pile.borrow().pile_type() == PileType::Suit(suit))
+    }
+
+    /// The pile the top `number` cards of `from` go to. A suit names the first
+    /// of its suit piles that takes the bottom card, or else the first empty one.
+    fn get_target(&self, number: usize, from: &PileRef, to: PileType) -> Result<PileRef> {
+        let PileType::Suit(suit) = to else {
+            return self.get_pile(to);
+        };
+        let from = from.borrow();
+        let card = from.length().checked_sub(number).and_then(|i| from.card(i));
+        let pile = self
+            .foundations_of(suit)
+            .find(|pile| card.is_some_and(|card| pile.borrow().can_add(card, &self.config)))
+            .or_else(|| {
+                self.foundations_of(suit)
+                    .find(|pile| pile.borrow().is_empty())
+            })
+            .or_else(|| self.foundations_of(suit).next())
+            .ok_or(Error::InvalidMove)?;
+        Ok(PileRef::clone(pile))
     }
 
     fn move_card(&self, card: Card, from: PileRef, to: PileRef) -> Result<()> {
@@ -78,7 +244,7 @@ impl Table {
         if let PileType::Uncovered = to.pile_type() {
             return Err(Box::new(Error::InvalidMove));
         }
-        if to.can_add(&card) && from.can_remove(&card) {
+        if to.can_add(&card, &self.config) && from.can_remove(&card) {
             let card = match from.remove_card(&card) {
                 Some(c) => c,
                 None => return Err(Box::new(Error::InvalidMove)),
@@ -97,12 +263,15 @@ impl Table {
         if number == 0 {
             return Err(Box::new(Error::InvalidMove));
         }
-        let from = self.get_pile(from);
-        let to = self.get_pile(to);
+        let from = self.get_pile(from)?;
+        let to = self.get_target(number, &from, to)?;
         if number == 1 {
             let card = *from.borrow().top_card().ok_or(Error::EmptyPile)?;
             return self.move_card(card, Rc::clone(&from), Rc::clone(&to));
         }
+        if !self.can_move(number, &from.borrow(), &to.borrow()) {
+            return Err(Box::new(Error::InvalidMove));
+        }
         let cards = from.borrow().get_cards(number).clone();
         let results = cards
             .into_iter()
@@ -118,19 +287,63 @@ impl Table {
         Ok(())
     }
 
-    pub fn draw_card(&self) -> Result<()> {
+    /// Moves the top `number` cards from one lane to another as a single
+    /// group. Only the bottom card of the group has to fit, the ones above it
+    /// just need to be face up.
+    pub fn move_group(&self, number: usize, from: PileType, to: PileType) -> Result<()> {
+        if !self.can_move_group(number, from, to) {
+            return Err(Box::new(Error::InvalidMove));
+        }
+        let (from, to) = (self.get_pile(from)?, self.get_pile(to)?);
+        let mut from = from.try_borrow_mut()?;
+        let mut to = to.try_borrow_mut()?;
+        let mut cards = from.get_cards(number);
+        (0..number).for_each(|_| {
+            from.remove_top_card();
+        });
+        to.add_all_cards(&mut cards);
+        if from.top_card_is_covered() {
+            from.flip_top_card();
+        }
+        Ok(())
+    }
+
+    pub fn can_move_group(&self, number: usize, from: PileType, to: PileType) -> bool {
+        let (from, to) = match (self.get_pile(from), self.get_pile(to)) {
+            (Ok(from), Ok(to)) if !Rc::ptr_eq(&from, &to) => (from, to),
+            _ => return false,
+        };
+        let (from, to) = (from.borrow(), to.borrow());
+        let lanes = (from.pile_type(), to.pile_type());
+        if !matches!(lanes, (PileType::Lane(_), PileType::Lane(_)))
+            || number == 0
+            || number > from.length()
+        {
+            return false;
+        }
+        let cards = from.get_cards(number);
+        to.can_add(&cards[0], &self.config) && cards.iter().all(|card| from.can_remove(card))
+    }
+
+    pub fn draw_card(&mut self) -> Result<()> {
         let mut draw_pile = self.draw_pile.try_borrow_mut()?;
         let mut uncovered_pile = self.uncovered_pile.try_borrow_mut()?;
         match draw_pile.top_card() {
-            Some(top_card) => {
-                let mut card = *top_card;
-                card.flip();
-                uncovered_pile.add_card(card);
-                draw_pile.remove_top_card();
+            Some(_) => {
+                for _ in 0..self.config.draw_count() {
+                    if let Some(mut card) = draw_pile.remove_top_card() {
+                        card.flip();
+                        uncovered_pile.add_card(card);
+                    }
+                }
                 Ok(())
             }
-            None if uncovered_pile.is_empty() => Ok(()),
+            None if uncovered_pile.is_empty() => Err(Box::new(Error::EmptyPile)),
+            None if self.config.max_passes().is_some_and(|max| self.pass >= max) => {
+                Err(Box::new(Error::NoPassesLeft))
+            }
             None => {
+                self.pass += 1;
                 draw_pile.add_all_cards(&mut uncovered_pile.remove_all_cards());
                 draw_pile.reverse();
                 draw_pile.flip_all_cards();
@@ -139,22 +352,172 @@ impl Table {
         }
     }
 
+    /// Deals a card face up straight onto the first suit pile that takes it.
+    pub fn deal_to_foundation(&self, mut card: Card) -> Result<()> {
+        if card.is_covered() {
+            card.flip();
+        }
+        let pile = self
+            .foundations
+            .iter()
+            .find(|pile| pile.borrow().can_add(&card, &self.config))
+            .ok_or(Error::InvalidMove)?;
+        pile.try_borrow_mut()?.add_card(card);
+        Ok(())
+    }
+
+    /// Deals a card face up onto the first empty free cell.
+    pub fn deal_to_cell(&self, mut card: Card) -> Result<()> {
+        if card.is_covered() {
+            card.flip();
+        }
+        let cell = self
+            .cells
+            .iter()
+            .find(|cell| cell.borrow().is_empty())
+            .ok_or(Error::InvalidMove)?;
+        cell.try_borrow_mut()?.add_card(card);
+        Ok(())
+    }
+
+    /// Takes the top `number` cards of a pile out of play, onto a suit pile of
+    /// the suit of the bottom one, whatever their order.
+    pub fn remove_cards(&self, number: usize, from: PileType) -> Result<()> {
+        let from = self.get_pile(from)?;
+        if number == 0 || number > from.borrow().length() {
+            return Err(Box::new(Error::InvalidMove));
+        }
+        let suit = from.borrow().get_cards(number)[0].suit();
+        let to = self.get_target(number, &from, PileType::Suit(suit))?;
+        let mut from = from.try_borrow_mut()?;
+        let mut cards = from.get_cards(number);
+        (0..number).for_each(|_| {
+            from.remove_top_card();
+        });
+        to.try_borrow_mut()?.add_all_cards(&mut cards);
+        if from.top_card_is_covered() {
+            from.flip_top_card();
+        }
+        Ok(())
+    }
+
+    /// Deals one card face up from the stock onto each lane. Every lane must
+    /// hold at least one card.
+    pub fn deal_to_lanes(&mut self) -> Result<()> {
+        let mut draw_pile = self.draw_pile.try_borrow_mut()?;
+        if draw_pile.is_empty() {
+            return Err(Box::new(Error::EmptyPile));
+        }
+        if self.lanes.iter().any(|lane| lane.borrow().is_empty()) {
+            return Err(Box::new(Error::EmptyLane));
+        }
+        for lane in &self.lanes {
+            if let Some(mut card) = draw_pile.remove_top_card() {
+                card.flip();
+                lane.try_borrow_mut()?.add_card(card);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the top `number` cards of a pile can be moved to another one,
+    /// card by card, so that no move is left half done.
+    pub fn can_move_cards(&self, number: usize, from: PileType, to: PileType) -> bool {
+        let Ok(from) = self.get_pile(from) else {
+            return false;
+        };
+        match self.get_target(number, &from, to) {
+            Ok(to) if !Rc::ptr_eq(&from, &to) => {
+                self.can_move(number, &from.borrow(), &to.borrow())
+            }
+            _ => false,
+        }
+    }
+
+    fn can_move(&self, number: usize, from: &Pile, to: &Pile) -> bool {
+        if number == 0 || number > from.length() || to.pile_type() == PileType::Uncovered {
+            return false;
+        }
+        let mut to = to.clone();
+        from.get_cards(number).into_iter().all(|card| {
+            let valid = to.can_add(&card, &self.config) && from.can_remove(&card);
+            to.add_card(card);
+            valid
+        })
+    }
+
     fn is_move_valid(&self, number: usize, from: PileRef, to: PileRef) -> bool {
         if number == 0 {
             return false;
         }
         let from = from.borrow();
         match from.card(from.length() - number) {
-            Some(card) => to.borrow().can_add(card),
+            Some(card) => to.borrow().can_add(card, &self.config),
             None => true,
         }
     }
 
+    /// Every move that can be played on the table under its own rules: drawing
+    /// from the stock, then moving from the waste, the reserve, the free cells,
+    /// the lanes and the suit piles, with each number of cards that fits.
+    /// Variants with rules of their own may still refuse some of them.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = vec![];
+        if self.can_draw() {
+            moves.push(Move::DrawCard);
+        }
+        let waste = self.uncovered_pile.borrow().pile_type();
+        let mut suits = vec![];
+        for pile in &self.foundations {
+            if let suit @ PileType::Suit(_) = pile.borrow().pile_type()
+                && !suits.contains(&suit)
+            {
+                suits.push(suit);
+            }
+        }
+        let lanes = (1..=self.lanes.len()).map(PileType::Lane);
+        let cells = (1..=self.cells.len()).map(PileType::Cell);
+        let sources = [waste, PileType::Reserve]
+            .into_iter()
+            .filter(|&pile| pile != PileType::Discard)
+            .chain(cells.clone())
+            .chain(lanes.clone())
+            .chain(suits.iter().copied());
+        let targets = lanes
+            .chain(suits.iter().copied())
+            .chain(cells)
+            .chain((waste == PileType::Discard).then_some(waste))
+            .collect::<Vec<PileType>>();
+        for from in sources {
+            // Only the lanes can move more than their top card.
+            let length = match (from, self.get_pile(from)) {
+                (PileType::Lane(_), Ok(pile)) => pile.borrow().length(),
+                (_, Ok(_)) => 1,
+                (_, Err(_)) => continue,
+            };
+            for &to in &targets {
+                if matches!((from, to), (PileType::Suit(_), PileType::Suit(_))) {
+                    continue;
+                }
+                let numbers = (1..=length).filter(|&n| self.can_move_cards(n, from, to));
+                moves.extend(numbers.map(|n| Move::MoveCards(n, from, to)));
+            }
+        }
+        moves
+    }
+
+    /// Whether [`Table::draw_card`] would draw or recycle the waste.
+    fn can_draw(&self) -> bool {
+        let passes_left = self.config.max_passes().is_none_or(|max| self.pass < max);
+        !self.draw_pile.borrow().is_empty()
+            || (!self.uncovered_pile.borrow().is_empty() && passes_left)
+    }
+
     pub fn auto_move(&self, from: PileType, to: PileType) -> Result<()> {
         let from_type = from;
         let to_type = to;
-        let from = self.get_pile(from_type);
-        let to = self.get_pile(to_type);
+        let from = self.get_pile(from_type)?;
+        let to = self.get_target(1, &from, to_type)?;
         if from.borrow().pile_type() == PileType::Uncovered {
             let card = *from.borrow().top_card().ok_or(Error::EmptyPile)?;
             return self.move_card(card, from, to);
@@ -172,7 +535,7 @@ impl Table {
 
 impl Default for Table {
     fn default() -> Self {
-        Table::new()
+        Table::new(rand::random(), GameConfig::default())
     }
 }
 
@@ -180,23 +543,32 @@ impl Clone for Table {
     fn clone(&self) -> Self {
         let draw_pile = Rc::new(RefCell::new(self.draw_pile.borrow().clone()));
         let uncovered_pile = Rc::new(RefCell::new(self.uncovered_pile.borrow().clone()));
+        let reserve = Rc::new(RefCell::new(self.reserve.borrow().clone()));
         let lanes = self
             .lanes
             .iter()
             .map(|lane| Rc::new(RefCell::new(lane.borrow().clone())))
             .collect();
+        let cells = self
+            .cells
+            .iter()
+            .map(|cell| Rc::new(RefCell::new(cell.borrow().clone())))
+            .collect();
 
-        let mut suit_piles = HashMap::new();
-        for pile in self.suit_piles.values() {
-            if let PileType::Suit(suit) = pile.borrow().pile_type() {
-                suit_piles.insert(suit, Rc::new(RefCell::new(pile.borrow().clone())));
-            }
-        }
+        let foundations = self
+            .foundations
+            .iter()
+            .map(|pile| Rc::new(RefCell::new(pile.borrow().clone())))
+            .collect();
         Table {
-            suit_piles,
+            foundations,
             lanes,
             draw_pile,
             uncovered_pile,
+            reserve,
+            cells,
+            config: self.config,
+            pass: self.pass,
         }
     }
 }
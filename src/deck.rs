@@ -3,36 +3,133 @@ pub mod card;
 use std::fmt::{Display, Formatter, Result};
 
 use card::{Card, Rank, Suit};
-use rand::{rng, seq::SliceRandom};
+use rand::{Rng, SeedableRng, rng, rngs::StdRng, seq::SliceRandom};
 use strum::IntoEnumIterator;
 
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const CODE_LENGTH: usize = 46;
+/// The number of cards in a single standard deck.
+pub const DECK_SIZE: usize = 52;
+
 pub struct Deck {
     cards: Vec<Card>,
 }
 
 impl Deck {
     pub fn new() -> Deck {
-        let mut cards = Suit::iter()
-            .flat_map(|suit| Rank::iter().map(move |rank| Card::new(rank, suit)))
+        Deck::with_seed(rng().random())
+    }
+
+    pub fn with_seed(seed: u64) -> Deck {
+        Deck::with_decks(seed, 1)
+    }
+
+    /// Shuffles `decks` standard decks together.
+    pub fn with_decks(seed: u64, decks: usize) -> Deck {
+        let suits = Suit::iter().cycle().take(4 * decks).collect::<Vec<Suit>>();
+        Deck::with_suits(seed, &suits)
+    }
+
+    /// Shuffles a full run of thirteen ranks for each of the given suits, which
+    /// may repeat, e.g. eight runs of spades for one-suit Spider.
+    pub fn with_suits(seed: u64, suits: &[Suit]) -> Deck {
+        let mut cards = suits
+            .iter()
+            .flat_map(|&suit| Rank::iter().map(move |rank| Card::new(rank, suit)))
             .collect::<Vec<Card>>();
 
-        cards.shuffle(&mut rng());
+        cards.shuffle(&mut StdRng::seed_from_u64(seed));
         Deck { cards }
     }
 
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
     pub fn pick_card(&mut self) -> Option<Card> {
         self.cards.pop()
     }
 
     pub fn pick_cards(&mut self, number: u8) -> Vec<Card> {
-        (0..number)
-            .filter_map(move |_| self.pick_card())
-            .collect()
+        (0..number).filter_map(move |_| self.pick_card()).collect()
     }
 
     pub fn pick_all_cards(self) -> Vec<Card> {
         self.cards
     }
+
+    /// Encodes the order of the cards as a base-32 number: the Lehmer code of
+    /// the permutation is converted digit by digit, so no big integers are needed.
+    /// Only a single standard deck has a code.
+    pub fn code(&self) -> Option<String> {
+        let mut seen = [false; DECK_SIZE];
+        if self.cards.len() != DECK_SIZE
+            || self
+                .cards
+                .iter()
+                .any(|card| std::mem::replace(&mut seen[card_index(card)], true))
+        {
+            return None;
+        }
+        let mut indexes = self.cards.iter().map(card_index).collect::<Vec<usize>>();
+        let mut digits = (0..indexes.len())
+            .map(|i| {
+                let smaller = indexes[i + 1..].iter().filter(|&&j| j < indexes[i]).count();
+                indexes[i] = smaller;
+                smaller
+            })
+            .collect::<Vec<usize>>();
+        let mut code = Vec::with_capacity(CODE_LENGTH);
+        while code.len() < CODE_LENGTH {
+            let mut remainder = 0;
+            for (i, digit) in digits.iter_mut().enumerate() {
+                let current = remainder * (DECK_SIZE - i) + *digit;
+                *digit = current / 32;
+                remainder = current % 32;
+            }
+            code.push(CODE_ALPHABET[remainder]);
+        }
+        Some(code.iter().rev().map(|&c| c as char).collect())
+    }
+
+    pub fn from_code(code: &str) -> Option<Deck> {
+        let code = code.trim().to_uppercase();
+        if code.len() != CODE_LENGTH {
+            return None;
+        }
+        let mut digits = vec![0; DECK_SIZE];
+        for c in code.bytes() {
+            let mut carry = CODE_ALPHABET.iter().position(|&a| a == c)?;
+            for (i, digit) in digits.iter_mut().enumerate().rev() {
+                let current = *digit * 32 + carry;
+                *digit = current % (DECK_SIZE - i);
+                carry = current / (DECK_SIZE - i);
+            }
+            if carry != 0 {
+                return None;
+            }
+        }
+        let mut remaining = (0..DECK_SIZE).collect::<Vec<usize>>();
+        let cards = digits
+            .into_iter()
+            .map(|digit| index_card(remaining.remove(digit)))
+            .collect();
+        Some(Deck { cards })
+    }
+}
+
+fn card_index(card: &Card) -> usize {
+    card.suit() as usize * 13 + card.rank() as usize - 1
+}
+
+fn index_card(index: usize) -> Card {
+    let suit = Suit::from_repr(index / 13).unwrap();
+    let rank = Rank::from_repr(index % 13 + 1).unwrap();
+    Card::new(rank, suit)
 }
 
 impl Display for Deck {
@@ -54,3 +151,72 @@ impl Default for Deck {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ordered_cards() -> Vec<Card> {
+        Suit::iter()
+            .flat_map(|suit| Rank::iter().map(move |rank| Card::new(rank, suit)))
+            .collect()
+    }
+
+    #[test]
+    fn code_round_trips() {
+        for seed in 0..200 {
+            let deck = Deck::with_seed(seed);
+            let code = deck.code().unwrap();
+            assert_eq!(code.len(), CODE_LENGTH);
+            let decoded = Deck::from_code(&code).unwrap();
+            assert_eq!(decoded.cards, deck.cards);
+        }
+    }
+
+    #[test]
+    fn ordered_deck_has_zero_code() {
+        let deck = Deck {
+            cards: ordered_cards(),
+        };
+        assert_eq!(deck.code().unwrap(), "0".repeat(CODE_LENGTH));
+    }
+
+    #[test]
+    fn code_one_swaps_the_last_two_cards() {
+        let code = format!("{}1", "0".repeat(CODE_LENGTH - 1));
+        let mut cards = ordered_cards();
+        cards.swap(DECK_SIZE - 2, DECK_SIZE - 1);
+        assert_eq!(Deck::from_code(&code).unwrap().cards, cards);
+    }
+
+    #[test]
+    fn lowercase_code_is_accepted() {
+        let code = Deck::with_seed(7).code().unwrap();
+        assert!(Deck::from_code(&code.to_lowercase()).is_some());
+    }
+
+    #[test]
+    fn too_large_code_is_rejected() {
+        assert!(Deck::from_code(&"Z".repeat(CODE_LENGTH)).is_none());
+    }
+
+    #[test]
+    fn wrong_length_code_is_rejected() {
+        assert!(Deck::from_code("").is_none());
+        assert!(Deck::from_code(&"0".repeat(CODE_LENGTH - 1)).is_none());
+        assert!(Deck::from_code(&"0".repeat(CODE_LENGTH + 1)).is_none());
+    }
+
+    #[test]
+    fn invalid_character_is_rejected() {
+        assert!(Deck::from_code(&format!("{}U", "0".repeat(CODE_LENGTH - 1))).is_none());
+    }
+
+    #[test]
+    fn decks_other_than_one_standard_deck_have_no_code() {
+        assert!(Deck::with_decks(0, 2).code().is_none());
+        let mut cards = ordered_cards();
+        cards[1] = cards[0];
+        assert!(Deck { cards }.code().is_none());
+    }
+}
@@ -2,10 +2,11 @@ use ansi_term::Colour::{Black, Green, Red, White};
 use std::{
     cmp::Ordering,
     fmt::{Display, Error, Formatter},
+    str::FromStr,
 };
 use strum_macros::{EnumIter, FromRepr};
 
-#[derive(Debug, Copy, Clone, EnumIter, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, EnumIter, PartialEq, Eq, Hash, FromRepr)]
 pub enum Suit {
     Clubs,
     Diamonds,
@@ -41,6 +42,25 @@ pub enum Colour {
     Red,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    Empty,
+    InvalidRank(String),
+    InvalidSuit(String),
+}
+
+impl std::error::Error for ParseCardError {}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::Empty => write!(f, "No card given"),
+            ParseCardError::InvalidRank(rank) => write!(f, "Invalid rank: {rank}"),
+            ParseCardError::InvalidSuit(suit) => write!(f, "Invalid suit: {suit}"),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Card {
     rank: Rank,
@@ -92,6 +112,11 @@ impl Card {
             self.rank as u8 == other.rank as u8 + 1
         }
     }
+
+    /// Like [`Card::is_next`], but an ace also comes after a king.
+    pub fn is_next_wrapping(&self, other: &Card) -> bool {
+        self.is_next(other) || (self.rank == Rank::Ace && other.rank == Rank::King)
+    }
 }
 
 impl PartialOrd for Card {
@@ -148,6 +173,58 @@ impl Display for Card {
     }
 }
 
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.trim().to_uppercase()[..] {
+            "C" | "♣" | "♧" | "CLUBS" => Ok(Suit::Clubs),
+            "D" | "♦" | "♢" | "DIAMONDS" => Ok(Suit::Diamonds),
+            "H" | "♥" | "♡" | "HEARTS" => Ok(Suit::Hearts),
+            "S" | "♠" | "♤" | "SPADES" => Ok(Suit::Spades),
+            "" => Err(ParseCardError::Empty),
+            _ => Err(ParseCardError::InvalidSuit(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.trim().to_uppercase()[..] {
+            "A" => Ok(Rank::Ace),
+            "T" => Ok(Rank::Ten),
+            "J" => Ok(Rank::Jack),
+            "Q" => Ok(Rank::Queen),
+            "K" => Ok(Rank::King),
+            "" => Err(ParseCardError::Empty),
+            r => r
+                .parse::<usize>()
+                .ok()
+                .filter(|n| (2..=10).contains(n))
+                .and_then(Rank::from_repr)
+                .ok_or(ParseCardError::InvalidRank(s.to_string())),
+        }
+    }
+}
+
+/// Parses cards written as rank followed by suit, e.g. `QS`, `10H`, `TH` or `A♣`.
+/// Parsed cards are covered, like the ones produced by [`Card::new`].
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (index, _) = s.char_indices().last().ok_or(ParseCardError::Empty)?;
+        let (rank, suit) = s.split_at(index);
+        if rank.is_empty() {
+            return Err(ParseCardError::InvalidRank(s.to_string()));
+        }
+        Ok(Card::new(rank.parse()?, suit.parse()?))
+    }
+}
+
 #[allow(dead_code)]
 fn repr(card: &Card) -> String {
     let mut s = String::from("1F0");
@@ -170,3 +247,61 @@ fn repr(card: &Card) -> String {
     let c = char::from_u32(h).unwrap();
     format!(" {} ", c)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(s: &str) -> Result<Card, ParseCardError> {
+        s.parse()
+    }
+
+    #[test]
+    fn parses_letter_ranks_and_suits() {
+        assert_eq!(card("QS"), Ok(Card::new(Rank::Queen, Suit::Spades)));
+        assert_eq!(card("qs"), Ok(Card::new(Rank::Queen, Suit::Spades)));
+        assert_eq!(card("KD"), Ok(Card::new(Rank::King, Suit::Diamonds)));
+    }
+
+    #[test]
+    fn parses_ten_as_number_or_letter() {
+        assert_eq!(card("10H"), Ok(Card::new(Rank::Ten, Suit::Hearts)));
+        assert_eq!(card("TH"), Ok(Card::new(Rank::Ten, Suit::Hearts)));
+    }
+
+    #[test]
+    fn parses_suit_symbols() {
+        assert_eq!(card("A♣"), Ok(Card::new(Rank::Ace, Suit::Clubs)));
+        assert_eq!(card("7♡"), Ok(Card::new(Rank::Seven, Suit::Hearts)));
+    }
+
+    #[test]
+    fn parsed_cards_are_covered() {
+        assert!(card("2C").unwrap().is_covered());
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert_eq!(card(""), Err(ParseCardError::Empty));
+        assert_eq!(card("  "), Err(ParseCardError::Empty));
+    }
+
+    #[test]
+    fn rejects_invalid_ranks() {
+        assert_eq!(card("1H"), Err(ParseCardError::InvalidRank("1".into())));
+        assert_eq!(card("11H"), Err(ParseCardError::InvalidRank("11".into())));
+        assert_eq!(card("H"), Err(ParseCardError::InvalidRank("H".into())));
+    }
+
+    #[test]
+    fn rejects_invalid_suits() {
+        assert_eq!(card("QX"), Err(ParseCardError::InvalidSuit("X".into())));
+    }
+
+    #[test]
+    fn parses_ranks_and_suits_alone() {
+        assert_eq!("J".parse(), Ok(Rank::Jack));
+        assert_eq!("spades".parse(), Ok(Suit::Spades));
+        assert_eq!("".parse::<Suit>(), Err(ParseCardError::Empty));
+    }
+}
@@ -1,34 +1,73 @@
-use crate::pile::Pile;
-use core::{Game, table::Table};
+use crate::pile::{Pile, PileType};
+use core::{Game, config::GameConfig, table::Table};
 use std::cell::Ref;
-use strum::IntoEnumIterator;
+use variant::Variant;
 
 pub mod core;
 pub mod deck;
 pub mod pile;
+pub mod solver;
+pub mod variant;
 
 const HELP_SCREEN: &str = "Controls:\n
     A                   Automatically finish the game if all cards are uncovered\n
     ?                   Print help\n
     Q | Esc             Quit game\n
     L                   Print move history\n
+    I                   Suggest a move, again for the next suggestion\n
     [Pile1][Pile2]      Automatically move cards from Pile1 to Pile2\n
     N                   Draw a card from the uncovered pile\n
-    U | Backspace       Undo last move\n
-    Pile can be any among 1-7, P, C, D, H, S";
+    U | Backspace       Undo last move\n";
 
-pub fn setup() -> Game {
-    Game::new()
+pub fn setup<V: Variant>(
+    variant: V,
+    deal: Option<&str>,
+    config: GameConfig,
+) -> Result<Game<V>, Box<dyn std::error::Error>> {
+    match deal {
+        Some(deal) => match deal.parse::<u64>() {
+            Ok(seed) => Ok(Game::from_seed(variant, seed, config)),
+            Err(_) => Game::from_code(variant, deal, config),
+        },
+        None => Ok(Game::new(variant, config)),
+    }
 }
 
 pub fn print_table(table: &Table) {
-    println!(" N\t P\t\t C\u{2663}\t D\u{2666}\t H\u{2665}\t S\u{2660}");
-    print!("{}\t{}\t\t", table.draw_pile(), table.uncovered_pile());
-    let suit_piles = deck::card::Suit::iter().map(|suit| table.suit_pile(suit));
-    suit_piles.for_each(|suit_pile| print!("{}\t", suit_pile));
+    println!(" N\t P\t\t{}", foundation_keys(table));
+    print!("{}\t{}\t\t", table.draw_pile(), waste(table));
+    print_foundations(table);
+    println!("\n");
+    print_lanes(table);
+}
+
+/// Prints the free cells followed by the suit piles.
+pub fn print_cells_and_suit_piles(table: &Table) {
+    let cells = (1..=table.cell_count())
+        .map(|i| format!(" {}\t", PileType::Cell(i)))
+        .collect::<String>();
+    println!("{}\t{}", cells, foundation_keys(table));
+    (0..table.cell_count()).for_each(|i| print!("{}\t", table.cell(i)));
+    print!("\t");
+    print_foundations(table);
     println!("\n");
+}
+
+fn foundation_keys(table: &Table) -> String {
+    (0..table.foundation_count())
+        .map(|i| match table.foundation(i).pile_type() {
+            PileType::Suit(suit) => format!(" {}{}\t", PileType::Suit(suit), suit),
+            pile_type => format!(" {}\t", pile_type),
+        })
+        .collect()
+}
 
-    let lanes = (0..7)
+fn print_foundations(table: &Table) {
+    (0..table.foundation_count()).for_each(|i| print!("{}\t", table.foundation(i)));
+}
+
+pub fn print_lanes(table: &Table) {
+    let lanes = (0..table.lane_count())
         .map(|i| table.lane(i))
         .collect::<Vec<Ref<'_, Pile>>>();
     lanes.iter().for_each(|lane| print!(" {}\t", lane));
@@ -50,6 +89,19 @@ pub fn print_table(table: &Table) {
     }
 }
 
-pub fn print_help() {
-    println!("{}", HELP_SCREEN);
+fn waste(table: &Table) -> String {
+    let waste = table.uncovered_pile();
+    if table.config().draw_count() == 1 || waste.length() < 2 {
+        return waste.to_string();
+    }
+    waste
+        .get_cards(waste.length().min(3))
+        .iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+pub fn print_help<V: Variant>(variant: &V) {
+    println!("{}    {}\n", HELP_SCREEN, variant.pile_keys());
 }
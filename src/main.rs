@@ -1,18 +1,162 @@
 use solitaire::{
     self,
-    core::{Error, moves::Move},
+    core::{
+        Error, Game, GameState,
+        config::{EmptyLane, GameConfig},
+        moves::Move,
+        score::{Bankroll, Scoring},
+    },
+    variant::{
+        BakersDozen, BeleagueredCastle, Canfield, FortyThieves, FreeCell, Golf, Klondike, Pyramid,
+        Spider, TriPeaks, Variant, Yukon,
+    },
+};
+use std::{
+    io::{Result, Write, stdin, stdout},
+    time::Duration,
 };
-use std::io::{Result, Write, stdin, stdout};
 use termion::{event::Key, input::TermRead, raw::IntoRawMode};
 
+const USAGE: &str = "Usage: solitaire [--variant NAME] [--draw-three] [--passes N] [--vegas] [--lanes N] [--any-empty-lane] [--winnable] [SEED | DEAL_CODE]
+Variants: klondike, spider, spider2, spider4, freecell, yukon, russian, golf, pyramid, tripeaks,
+fortythieves, canfield, bakersdozen, beleaguered, bakersgame, seahaven, eightoff";
+const STALEMATE: &str =
+    "No move leads anywhere but back and forth. U: undo, R: restart the deal, D: new deal, Q: quit";
+/// How long to look for a winnable deal before settling for a random one.
+const WINNABLE_TIME: Duration = Duration::from_secs(5);
+
 fn main() -> Result<()> {
-    let mut game = solitaire::setup();
-    while !game.is_over() {
+    let mut deal = None;
+    let mut variant = String::from("klondike");
+    let mut winnable = false;
+    let mut config = GameConfig::builder();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        config = match &arg[..] {
+            "-3" | "--draw-three" => config.draw_count(3),
+            "-v" | "--vegas" => config.scoring(Scoring::Vegas),
+            "-e" | "--any-empty-lane" => config.empty_lane(EmptyLane::Any),
+            "-w" | "--winnable" => {
+                winnable = true;
+                config
+            }
+            "-p" | "--passes" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(n) => config.max_passes(Some(n)),
+                None => {
+                    eprintln!("{}", USAGE);
+                    return Ok(());
+                }
+            },
+            "-V" | "--variant" => match args.next() {
+                Some(name) => {
+                    variant = name.to_lowercase();
+                    config
+                }
+                None => {
+                    eprintln!("{}", USAGE);
+                    return Ok(());
+                }
+            },
+            "-l" | "--lanes" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(n) => config.lanes(n),
+                None => {
+                    eprintln!("{}", USAGE);
+                    return Ok(());
+                }
+            },
+            _ => {
+                deal = Some(arg);
+                config
+            }
+        }
+    }
+    let (deal, config) = (deal.as_deref(), config.build());
+    if winnable && variant != "klondike" {
+        eprintln!("Winnable deals are only available in Klondike\n{}", USAGE);
+        return Ok(());
+    }
+    if winnable && deal.is_some() {
+        eprintln!(
+            "Winnable deals cannot be combined with a seed or deal code\n{}",
+            USAGE
+        );
+        return Ok(());
+    }
+    match &variant[..] {
+        "klondike" if winnable => run(Game::new_winnable(config, WINNABLE_TIME)),
+        "klondike" => start(Klondike, deal, config),
+        "spider" | "spider1" => start(Spider::new(1), deal, config),
+        "spider2" => start(Spider::new(2), deal, config),
+        "spider4" => start(Spider::new(4), deal, config),
+        "freecell" => start(FreeCell::new(), deal, config),
+        "bakersgame" => start(FreeCell::bakers_game(), deal, config),
+        "seahaven" => start(FreeCell::seahaven_towers(), deal, config),
+        "eightoff" => start(FreeCell::eight_off(), deal, config),
+        "yukon" => start(Yukon::new(), deal, config),
+        "russian" => start(Yukon::russian(), deal, config),
+        "golf" => start(Golf, deal, config),
+        "pyramid" => start(Pyramid, deal, config),
+        "tripeaks" => start(TriPeaks, deal, config),
+        "fortythieves" => start(FortyThieves, deal, config),
+        "canfield" => start(Canfield, deal, config),
+        "bakersdozen" => start(BakersDozen, deal, config),
+        "beleaguered" => start(BeleagueredCastle, deal, config),
+        _ => {
+            eprintln!("Unknown variant: {}\n{}", variant, USAGE);
+            Ok(())
+        }
+    }
+}
+
+fn start<V: Variant>(variant: V, deal: Option<&str>, config: GameConfig) -> Result<()> {
+    match solitaire::setup(variant, deal, config) {
+        Ok(game) => run(game),
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            Ok(())
+        }
+    }
+}
+
+fn run<V: Variant>(mut game: Game<V>) -> Result<()> {
+    if game.config().scoring() == Scoring::Vegas {
+        game = game.with_bankroll(Bankroll::load()?);
+    }
+    loop {
+        let state = game.state();
+        if state == GameState::Won {
+            break;
+        }
+        game.save_bankroll()?;
         clear_screen()?;
         println!("\n{game}");
-        solitaire::print_table(game.table());
+        game.variant().print_table(game.table());
+        if state == GameState::Stalemate {
+            game.pause_clock();
+            println!("\n{}", ansi_term::Colour::Red.paint(STALEMATE));
+            match take_key()? {
+                Key::Backspace | Key::Char('u') => {
+                    let _ = game.play(Move::Undo);
+                }
+                Key::Char('r') => game.restart(),
+                Key::Char('d') => game.redeal(),
+                Key::Esc | Key::Char('q') => {
+                    clear_screen()?;
+                    return Ok(());
+                }
+                _ => (),
+            }
+            game.resume_clock();
+            continue;
+        }
+        if let Some(hint) = game.hint() {
+            println!(
+                "\n{}",
+                ansi_term::Colour::Green.paint(format!("Hint: {}", game.variant().move_keys(hint)))
+            );
+        }
         let input = take_input()?;
-        let next_move = Move::from(input);
+        let next_move = Move::parse(&input, game.variant());
         if let Err(e) = game.play(next_move) {
             match e.downcast::<Error>() {
                 Ok(err) => match err.as_ref() {
@@ -21,11 +165,14 @@ fn main() -> Result<()> {
                         return Ok(());
                     }
                     Error::Help => {
+                        game.pause_clock();
                         clear_screen()?;
-                        solitaire::print_help();
+                        solitaire::print_help(game.variant());
+                        println!("{}\n", game.config());
                         println!("Press Enter to continue");
                     }
                     Error::History => {
+                        game.pause_clock();
                         clear_screen()?;
                         game.print_history();
                         println!("Press Enter to continue");
@@ -44,8 +191,11 @@ fn main() -> Result<()> {
                 }
             }
             let _ = take_input();
+            game.resume_clock();
         }
     }
+    game.pause_clock();
+    game.save_bankroll()?;
     clear_screen()?;
     let mut stdout = stdout().into_raw_mode()?;
     writeln!(
@@ -55,6 +205,13 @@ fn main() -> Result<()> {
         termion::cursor::Goto(1, 2)
     )?;
     println!("{game}");
+    if game.config().scoring() == Scoring::Standard {
+        println!(
+            "Time bonus: {}\tFinal score: {}",
+            game.time_bonus(),
+            game.final_score()
+        );
+    }
     Ok(())
 }
 
@@ -75,6 +232,7 @@ fn take_input() -> Result<String> {
             Key::Char('?') => return Ok(String::from("?")),
             Key::Char('n') => return Ok(String::from("N")),
             Key::Char('a') => return Ok(String::from("A")),
+            Key::Char('i') => return Ok(String::from("I")),
             Key::Backspace | Key::Char('u') => return Ok(String::from("U")),
             Key::Esc | Key::Char('q') => return Ok(String::from("Q")),
             Key::Char(c) => {
@@ -92,6 +250,14 @@ fn take_input() -> Result<String> {
     Ok(input)
 }
 
+fn take_key() -> Result<Key> {
+    let _stdout = stdout().into_raw_mode()?;
+    match stdin().keys().next() {
+        Some(key) => key,
+        None => Ok(Key::Esc),
+    }
+}
+
 fn clear_screen() -> Result<()> {
     let mut stdout = stdout().into_raw_mode()?;
     write!(
@@ -1,6 +1,12 @@
 use std::fmt::{Display, Error, Formatter};
 
-use crate::deck::card::{self, Card, Rank};
+use crate::{
+    core::config::{Build, EmptyLane, GameConfig},
+    deck::card::{self, Card, Rank},
+};
+
+/// Keys of the free cells, in order.
+pub const CELL_KEYS: [char; 8] = ['W', 'X', 'Y', 'Z', 'R', 'T', 'V', 'O'];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PileType {
@@ -8,6 +14,9 @@ pub enum PileType {
     Suit(card::Suit),
     Draw,
     Uncovered,
+    Cell(usize),
+    Discard,
+    Reserve,
 }
 
 impl Display for PileType {
@@ -21,7 +30,11 @@ impl Display for PileType {
                 card::Suit::Spades => "S",
             },
             PileType::Draw => "D",
-            PileType::Uncovered => "P",
+            PileType::Uncovered | PileType::Discard => "P",
+            PileType::Reserve => "R",
+            PileType::Cell(n) => &CELL_KEYS
+                .get(n.wrapping_sub(1))
+                .map_or(format!("F{n}"), |key| key.to_string())[..],
         };
         write!(f, "{}", pile)
     }
@@ -75,17 +88,44 @@ impl Pile {
     pub fn top_card(&self) -> Option<&Card> {
         self.cards.last()
     }
-    pub fn can_add(&self, card: &Card) -> bool {
+    pub fn can_add(&self, card: &Card, config: &GameConfig) -> bool {
         match self.pile_type {
             PileType::Uncovered => true,
             PileType::Draw => true,
+            PileType::Reserve => false,
+            PileType::Cell(_) => self.cards.is_empty(),
+            PileType::Discard => match self.top_card() {
+                Some(top_card) if config.wrap() => {
+                    top_card.is_next_wrapping(card) || card.is_next_wrapping(top_card)
+                }
+                Some(top_card) => top_card.is_next(card) || card.is_next(top_card),
+                None => true,
+            },
             PileType::Suit(suit) => match self.top_card() {
+                Some(top_card) if config.wrap() => {
+                    card.suit() == suit && card.is_next_wrapping(top_card)
+                }
                 Some(top_card) => card.suit() == suit && card.is_next(top_card),
-                None => card.suit() == suit && card.rank() == Rank::Ace,
+                None => card.suit() == suit && card.rank() == config.base_rank(),
             },
             PileType::Lane(_) => match self.top_card() {
-                Some(top_card) => top_card.colour() != card.colour() && top_card.is_next(card),
-                None => card.rank() == Rank::King,
+                Some(top_card) => {
+                    let builds = match config.build_rule() {
+                        Build::AlternateColours => top_card.colour() != card.colour(),
+                        Build::SameSuit => top_card.suit() == card.suit(),
+                        Build::AnySuit => true,
+                    };
+                    let next = match config.wrap() {
+                        true => top_card.is_next_wrapping(card),
+                        false => top_card.is_next(card),
+                    };
+                    builds && next
+                }
+                None => match config.empty_lane() {
+                    EmptyLane::Kings => card.rank() == Rank::King,
+                    EmptyLane::Any => true,
+                    EmptyLane::None => false,
+                },
             },
         }
     }
@@ -142,7 +182,12 @@ impl Pile {
 impl Display for Pile {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         let s = match self.pile_type {
-            PileType::Draw | PileType::Uncovered | PileType::Suit(_) => match self.cards.last() {
+            PileType::Draw
+            | PileType::Uncovered
+            | PileType::Suit(_)
+            | PileType::Cell(_)
+            | PileType::Discard
+            | PileType::Reserve => match self.cards.last() {
                 Some(card) => card.to_string(),
                 None => "░░░".to_string(),
             },
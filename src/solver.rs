@@ -0,0 +1,566 @@
+//! A depth-first solver for Klondike deals. It knows where every face-down
+//! card is, and either finds a winning sequence of moves or explores every
+//! position reachable from the table, which proves that the deal cannot be
+//! won.
+//!
+//! Suit piles that wrap around from king to ace, or that start from another
+//! rank than the ace, are not modeled: such tables are left unsolved.
+
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    core::{
+        config::{Build, EmptyLane},
+        moves::Move,
+        table::Table,
+    },
+    deck::card::{Card, Rank, Suit},
+    pile::PileType,
+};
+
+const SUITS: usize = 4;
+const KING: u8 = 13;
+/// How many positions are explored between two checks of the clock.
+const CLOCK_INTERVAL: usize = 1024;
+
+/// The outcome of a search.
+#[derive(Debug, Clone)]
+pub enum Solution {
+    /// Moves that win the game when played in order. The number of positions
+    /// explored to find them measures how hard the deal is.
+    Win { moves: Vec<Move>, positions: usize },
+    /// Every position reachable from the table was explored without finding a
+    /// win, so there is none.
+    Unwinnable { positions: usize },
+    /// The search ran out of its budget before reaching an answer, or the
+    /// rules of the table are not modeled by the solver.
+    Unknown,
+}
+
+/// Searches for a way to win a Klondike table within a budget of positions
+/// and time.
+#[derive(Debug, Clone, Copy)]
+pub struct Solver {
+    max_positions: usize,
+    time_limit: Duration,
+}
+
+impl Solver {
+    pub fn new() -> Solver {
+        Solver {
+            max_positions: 500_000,
+            time_limit: Duration::from_secs(5),
+        }
+    }
+
+    /// The number of distinct positions explored before giving up.
+    pub fn max_positions(mut self, max_positions: usize) -> Self {
+        self.max_positions = max_positions;
+        self
+    }
+
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = time_limit;
+        self
+    }
+
+    pub fn solve(&self, table: &Table) -> Solution {
+        let config = table.config();
+        if config.wrap() || config.base_rank() != Rank::Ace {
+            return Solution::Unknown;
+        }
+        let mut search = Search {
+            rules: Rules::new(table),
+            seen: HashSet::new(),
+            path: vec![],
+            deadline: Instant::now() + self.time_limit,
+            max_positions: self.max_positions,
+            out_of_budget: false,
+        };
+        let mut state = State::new(table);
+        search.path.extend(state.auto_play(&search.rules));
+        if search.explore(state) {
+            Solution::Win {
+                moves: search.path.into_iter().map(Step::into_move).collect(),
+                positions: search.seen.len(),
+            }
+        } else if search.out_of_budget {
+            Solution::Unknown
+        } else {
+            Solution::Unwinnable {
+                positions: search.seen.len(),
+            }
+        }
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::new()
+    }
+}
+
+/// Cards are numbered from 0, thirteen by suit, ace first.
+fn card_id(card: &Card) -> u8 {
+    card.suit() as u8 * KING + card.rank() as u8 - 1
+}
+
+fn suit(card: u8) -> usize {
+    (card / KING) as usize
+}
+
+fn rank(card: u8) -> u8 {
+    card % KING + 1
+}
+
+fn is_red(card: u8) -> bool {
+    matches!(
+        Suit::from_repr(suit(card)),
+        Some(Suit::Diamonds | Suit::Hearts)
+    )
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rules {
+    draw_count: usize,
+    max_passes: Option<u32>,
+    empty_lane: EmptyLane,
+    build_rule: Build,
+}
+
+impl Rules {
+    fn new(table: &Table) -> Rules {
+        let config = table.config();
+        Rules {
+            draw_count: config.draw_count(),
+            max_passes: config.max_passes(),
+            empty_lane: config.empty_lane(),
+            build_rule: config.build_rule(),
+        }
+    }
+
+    /// Whether `card` can be placed on a lane whose top card is `top`.
+    fn fits(&self, card: u8, top: Option<u8>) -> bool {
+        match top {
+            Some(top) => {
+                let builds = match self.build_rule {
+                    Build::AlternateColours => is_red(top) != is_red(card),
+                    Build::SameSuit => suit(top) == suit(card),
+                    Build::AnySuit => true,
+                };
+                builds && rank(top) == rank(card) + 1
+            }
+            None => match self.empty_lane {
+                EmptyLane::Kings => rank(card) == KING,
+                EmptyLane::Any => true,
+                EmptyLane::None => false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pile {
+    Lane(usize),
+    Waste,
+    Foundation(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Draw,
+    Move(usize, Pile, Pile),
+}
+
+impl Step {
+    fn into_move(self) -> Move {
+        let pile_type = |pile| match pile {
+            Pile::Lane(i) => PileType::Lane(i + 1),
+            Pile::Waste => PileType::Uncovered,
+            Pile::Foundation(suit) => {
+                PileType::Suit(Suit::from_repr(suit).expect("foundations are indexed by suit"))
+            }
+        };
+        match self {
+            Step::Draw => Move::DrawCard,
+            Step::Move(n, from, to) => Move::MoveCards(n, pile_type(from), pile_type(to)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Lane {
+    cards: Vec<u8>,
+    face_down: usize,
+}
+
+impl Lane {
+    fn top(&self) -> Option<u8> {
+        self.cards.last().copied()
+    }
+
+    fn face_up(&self) -> usize {
+        self.cards.len() - self.face_down
+    }
+
+    fn remove(&mut self, number: usize) -> Vec<u8> {
+        let cards = self.cards.split_off(self.cards.len() - number);
+        if self.face_down > 0 && self.face_down == self.cards.len() {
+            self.face_down -= 1;
+        }
+        cards
+    }
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    lanes: Vec<Lane>,
+    stock: Vec<u8>,
+    waste: Vec<u8>,
+    /// The rank of the top card of each suit pile, 0 when empty.
+    foundations: [u8; SUITS],
+    pass: u32,
+}
+
+impl State {
+    fn new(table: &Table) -> State {
+        let lanes = (0..table.lane_count())
+            .map(|i| {
+                let cards = table.lane(i).cards();
+                Lane {
+                    face_down: cards.iter().filter(|card| card.is_covered()).count(),
+                    cards: cards.iter().map(card_id).collect(),
+                }
+            })
+            .collect();
+        let mut foundations = [0; SUITS];
+        for i in 0..table.foundation_count() {
+            if let Some(card) = table.foundation(i).top_card() {
+                let card = card_id(card);
+                foundations[suit(card)] = foundations[suit(card)].max(rank(card));
+            }
+        }
+        State {
+            lanes,
+            stock: table.draw_pile().cards().iter().map(card_id).collect(),
+            waste: table.uncovered_pile().cards().iter().map(card_id).collect(),
+            foundations,
+            pass: table.pass(),
+        }
+    }
+
+    fn is_won(&self) -> bool {
+        self.foundations.iter().all(|&rank| rank == KING)
+    }
+
+    fn top(&self, pile: Pile) -> Option<u8> {
+        match pile {
+            Pile::Lane(i) => self.lanes[i].top(),
+            Pile::Waste => self.waste.last().copied(),
+            Pile::Foundation(suit) => match self.foundations[suit] {
+                0 => None,
+                rank => Some(suit as u8 * KING + rank - 1),
+            },
+        }
+    }
+
+    fn to_foundation(&self, card: u8) -> bool {
+        self.foundations[suit(card)] + 1 == rank(card)
+    }
+
+    /// A card can go to its suit pile for good once every card that could
+    /// need it as a base in the lanes is itself on the suit piles. When
+    /// building by suit that is only the card below it, which is already
+    /// there. With any suit it is every card one rank lower. With alternate
+    /// colours it is the cards of the other colour one rank lower, and those
+    /// of the other suit of its colour two ranks lower, which could need them.
+    fn is_safe(&self, card: u8, rules: &Rules) -> bool {
+        let (suit, rank) = (suit(card), rank(card));
+        let others = || (0..SUITS).filter(move |&other| other != suit);
+        rank <= 2
+            || match rules.build_rule {
+                Build::SameSuit => true,
+                Build::AnySuit => others().all(|other| self.foundations[other] >= rank - 1),
+                Build::AlternateColours => others().all(|other| {
+                    let same_colour = is_red(other as u8 * KING) == is_red(card);
+                    let needed = if same_colour { rank - 2 } else { rank - 1 };
+                    self.foundations[other] >= needed
+                }),
+            }
+    }
+
+    /// Plays the safe moves to the suit piles until there are none left.
+    fn auto_play(&mut self, rules: &Rules) -> Vec<Step> {
+        let mut steps = vec![];
+        loop {
+            let from = (0..self.lanes.len())
+                .map(Pile::Lane)
+                .chain([Pile::Waste])
+                .find(|&pile| {
+                    self.top(pile)
+                        .is_some_and(|card| self.to_foundation(card) && self.is_safe(card, rules))
+                });
+            let Some(from) = from else {
+                return steps;
+            };
+            let card = self.top(from).expect("the pile has a top card");
+            let step = Step::Move(1, from, Pile::Foundation(suit(card)));
+            self.apply(step, rules);
+            steps.push(step);
+        }
+    }
+
+    fn can_draw(&self, rules: &Rules) -> bool {
+        !self.stock.is_empty()
+            || (!self.waste.is_empty() && rules.max_passes.is_none_or(|max| self.pass < max))
+    }
+
+    /// Plays a move the way the table does, turning the waste over into the
+    /// stock when drawing from an empty stock.
+    fn apply(&mut self, step: Step, rules: &Rules) {
+        match step {
+            Step::Draw if self.stock.is_empty() => {
+                self.pass += 1;
+                self.stock = self.waste.drain(..).rev().collect();
+            }
+            Step::Draw => {
+                for _ in 0..rules.draw_count {
+                    if let Some(card) = self.stock.pop() {
+                        self.waste.push(card);
+                    }
+                }
+            }
+            Step::Move(n, from, to) => {
+                let cards = match from {
+                    Pile::Lane(i) => self.lanes[i].remove(n),
+                    Pile::Waste => vec![self.waste.pop().expect("the waste has a top card")],
+                    Pile::Foundation(suit) => {
+                        self.foundations[suit] -= 1;
+                        vec![suit as u8 * KING + self.foundations[suit]]
+                    }
+                };
+                match to {
+                    Pile::Lane(i) => self.lanes[i].cards.extend(cards),
+                    Pile::Foundation(suit) => self.foundations[suit] += n as u8,
+                    Pile::Waste => unreachable!("nothing is moved to the waste"),
+                }
+            }
+        }
+    }
+
+    /// Every move from this position, the most promising first.
+    fn steps(&self, rules: &Rules) -> Vec<Step> {
+        let lanes = self.lanes.len();
+        let mut uncovering = vec![];
+        let mut others = vec![];
+        for from in 0..lanes {
+            let lane = &self.lanes[from];
+            if let Some(card) = lane.top()
+                && self.to_foundation(card)
+            {
+                uncovering.push(Step::Move(
+                    1,
+                    Pile::Lane(from),
+                    Pile::Foundation(suit(card)),
+                ));
+            }
+            for n in 1..=lane.face_up() {
+                let card = lane.cards[lane.cards.len() - n];
+                let whole_lane = n == lane.cards.len();
+                for to in (0..lanes).filter(|&to| to != from) {
+                    let top = self.lanes[to].top();
+                    // Moving a whole lane to an empty one changes nothing.
+                    if (whole_lane && top.is_none()) || !rules.fits(card, top) {
+                        continue;
+                    }
+                    let step = Step::Move(n, Pile::Lane(from), Pile::Lane(to));
+                    match n == lane.face_up() && lane.face_down > 0 {
+                        true => uncovering.push(step),
+                        false => others.push(step),
+                    }
+                }
+            }
+        }
+        let mut steps = uncovering;
+        if let Some(card) = self.waste.last().copied() {
+            if self.to_foundation(card) {
+                steps.push(Step::Move(1, Pile::Waste, Pile::Foundation(suit(card))));
+            }
+            for to in (0..lanes).filter(|&to| rules.fits(card, self.lanes[to].top())) {
+                steps.push(Step::Move(1, Pile::Waste, Pile::Lane(to)));
+            }
+        }
+        if self.can_draw(rules) {
+            steps.push(Step::Draw);
+        }
+        steps.append(&mut others);
+        for suit in 0..SUITS {
+            let Some(card) = self.top(Pile::Foundation(suit)) else {
+                continue;
+            };
+            for to in (0..lanes).filter(|&to| rules.fits(card, self.lanes[to].top())) {
+                steps.push(Step::Move(1, Pile::Foundation(suit), Pile::Lane(to)));
+            }
+        }
+        steps
+    }
+
+    /// Identifies the position regardless of the order of the lanes, and of
+    /// the pass through the stock when there is no limit on passes.
+    fn key(&self, rules: &Rules) -> Vec<u8> {
+        let mut lanes = self
+            .lanes
+            .iter()
+            .map(|lane| {
+                let mut key = vec![lane.face_down as u8];
+                key.extend(&lane.cards);
+                key
+            })
+            .collect::<Vec<Vec<u8>>>();
+        lanes.sort();
+        let mut key = self.foundations.to_vec();
+        if rules.max_passes.is_some() {
+            key.push(self.pass as u8);
+        }
+        key.push(self.stock.len() as u8);
+        key.extend(&self.stock);
+        key.push(self.waste.len() as u8);
+        key.extend(&self.waste);
+        for lane in lanes {
+            key.push(lane.len() as u8);
+            key.extend(lane);
+        }
+        key
+    }
+}
+
+struct Search {
+    rules: Rules,
+    seen: HashSet<Vec<u8>>,
+    path: Vec<Step>,
+    deadline: Instant,
+    max_positions: usize,
+    out_of_budget: bool,
+}
+
+impl Search {
+    /// Whether the game can be won from the position, leaving the winning
+    /// moves in the path.
+    fn explore(&mut self, state: State) -> bool {
+        if state.is_won() {
+            return true;
+        }
+        if !self.seen.insert(state.key(&self.rules)) {
+            return false;
+        }
+        if self.seen.len() >= self.max_positions
+            || (self.seen.len().is_multiple_of(CLOCK_INTERVAL) && Instant::now() >= self.deadline)
+        {
+            self.out_of_budget = true;
+        }
+        if self.out_of_budget {
+            return false;
+        }
+        for step in state.steps(&self.rules) {
+            let mut next = state.clone();
+            next.apply(step, &self.rules);
+            let length = self.path.len();
+            self.path.push(step);
+            self.path.extend(next.auto_play(&self.rules));
+            if self.explore(next) {
+                return true;
+            }
+            self.path.truncate(length);
+            if self.out_of_budget {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::config::GameConfig,
+        variant::{Klondike, Variant},
+    };
+
+    fn deal(seed: u64, config: GameConfig) -> Table {
+        Klondike.deal(Klondike.deck(seed), config)
+    }
+
+    #[test]
+    fn winning_moves_replay() {
+        for (draw_count, seeds) in [(1, [5, 10, 12, 16, 20]), (3, [8, 10, 13, 16, 3])] {
+            let config = GameConfig::builder().draw_count(draw_count).build();
+            for seed in seeds {
+                let mut table = deal(seed, config);
+                let solution = Solver::new()
+                    .max_positions(2_000)
+                    .time_limit(Duration::from_secs(60))
+                    .solve(&table);
+                let Solution::Win { moves, .. } = solution else {
+                    panic!("no win found for seed {seed}, drawing {draw_count}");
+                };
+                for game_move in moves {
+                    Klondike.play(&mut table, game_move).unwrap();
+                }
+                assert!(Klondike.is_won(&table), "seed {seed}, drawing {draw_count}");
+            }
+        }
+    }
+
+    #[test]
+    fn single_pass_deal_is_unwinnable() {
+        let config = GameConfig::builder()
+            .draw_count(3)
+            .max_passes(Some(1))
+            .build();
+        let solution = Solver::new().solve(&deal(2, config));
+        assert!(matches!(solution, Solution::Unwinnable { .. }));
+    }
+
+    #[test]
+    fn small_budget_is_unknown() {
+        let solution = Solver::new()
+            .max_positions(1)
+            .solve(&deal(0, GameConfig::default()));
+        assert!(matches!(solution, Solution::Unknown));
+    }
+
+    #[test]
+    fn other_build_rules_replay() {
+        for (build_rule, seeds) in [(Build::SameSuit, [11, 22, 26]), (Build::AnySuit, [1, 2, 3])] {
+            let config = GameConfig::builder().build_rule(build_rule).build();
+            for seed in seeds {
+                let mut table = deal(seed, config);
+                let solution = Solver::new().max_positions(2_000).solve(&table);
+                let Solution::Win { moves, .. } = solution else {
+                    panic!("no win found for seed {seed}, building {build_rule:?}");
+                };
+                for game_move in moves {
+                    Klondike.play(&mut table, game_move).unwrap();
+                }
+                assert!(
+                    Klondike.is_won(&table),
+                    "seed {seed}, building {build_rule:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unmodeled_suit_piles_are_unknown() {
+        let wrapping = GameConfig::builder().wrap(true).build();
+        let base_rank = GameConfig::builder().base_rank(Rank::Seven).build();
+        for config in [wrapping, base_rank] {
+            let solution = Solver::new().solve(&deal(0, config));
+            assert!(matches!(solution, Solution::Unknown));
+        }
+    }
+}
@@ -0,0 +1,148 @@
+pub mod bakers_dozen;
+pub mod beleaguered_castle;
+pub mod canfield;
+pub mod forty_thieves;
+pub mod freecell;
+pub mod golf;
+pub mod klondike;
+pub mod pyramid;
+pub mod spider;
+pub mod tripeaks;
+pub mod yukon;
+
+pub use bakers_dozen::BakersDozen;
+pub use beleaguered_castle::BeleagueredCastle;
+pub use canfield::Canfield;
+pub use forty_thieves::FortyThieves;
+pub use freecell::FreeCell;
+pub use golf::Golf;
+pub use klondike::Klondike;
+pub use pyramid::Pyramid;
+pub use spider::Spider;
+pub use tripeaks::TriPeaks;
+pub use yukon::Yukon;
+
+use crate::{
+    core::{Error, Result, config::GameConfig, moves::Move, table::Table},
+    deck::{Deck, card::Suit},
+    pile::PileType,
+};
+
+/// A solitaire game that can be played by the engine. A variant sets up the
+/// table, decides which moves are legal and when the game is won, while
+/// [`Game`](crate::core::Game) takes care of history, undo and scoring.
+pub trait Variant {
+    fn name(&self) -> &str;
+
+    /// The rules used to build piles, starting from the ones chosen by the player.
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+    }
+
+    fn deck(&self, seed: u64) -> Deck {
+        Deck::with_seed(seed)
+    }
+
+    /// Deals the initial layout.
+    fn deal(&self, deck: Deck, config: GameConfig) -> Table;
+
+    /// Plays a move on the table, failing if it is not legal.
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        match game_move {
+            Move::DrawCard => table.draw_card(),
+            Move::AutoMove(from, to) => table.auto_move(from, to),
+            Move::MoveCards(n, from, to) => table.move_cards(n, from, to),
+            _ => Err(Box::new(Error::InvalidMove)),
+        }
+    }
+
+    /// Every move the variant accepts on the table.
+    fn legal_moves(&self, table: &Table) -> Vec<Move> {
+        table
+            .legal_moves()
+            .into_iter()
+            .filter(|&game_move| self.play(&mut table.clone(), game_move).is_ok())
+            .collect()
+    }
+
+    /// Whether the game can be finished by sending every card to the suit piles.
+    fn can_auto_finish(&self, _table: &Table) -> bool {
+        false
+    }
+
+    fn is_won(&self, table: &Table) -> bool {
+        table.suit_cards() == 52
+    }
+
+    /// Maps a key to the pile it stands for.
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        match c {
+            'P' => Some(PileType::Uncovered),
+            c => suit_key(c).or(lane_key(c)),
+        }
+    }
+
+    /// The key standing for a pile, the inverse of [`Variant::parse_pile`].
+    fn pile_key(&self, pile: PileType) -> Option<char> {
+        ('0'..='9')
+            .chain('A'..='Z')
+            .find(|&c| self.parse_pile(c) == Some(pile))
+    }
+
+    /// The keys to type to play a move, e.g. to show a hint. Moves between
+    /// piles are typed as the keys of the two piles.
+    fn move_keys(&self, game_move: Move) -> String {
+        match game_move {
+            Move::AutoMove(from, to)
+            | Move::MoveCards(_, from, to)
+            | Move::RemovePair(from, to) => match (self.pile_key(from), self.pile_key(to)) {
+                (Some(from), Some(to)) => format!("{from}{to}"),
+                _ => game_move.to_string(),
+            },
+            _ => game_move.to_string(),
+        }
+    }
+
+    /// The move played by naming two piles.
+    fn pile_move(&self, from: PileType, to: PileType) -> Move {
+        Move::AutoMove(from, to)
+    }
+
+    /// Describes the keys of the piles for the help screen.
+    fn pile_keys(&self) -> &str {
+        "Pile can be any among the lane numbers (0 for lane 10), P, C, D, H, S"
+    }
+
+    fn print_table(&self, table: &Table) {
+        crate::print_table(table);
+    }
+}
+
+/// Plays a move for games where cards can only be moved one at a time.
+pub fn play_single_cards(table: &mut Table, game_move: Move) -> Result<()> {
+    match game_move {
+        Move::DrawCard => table.draw_card(),
+        Move::AutoMove(from, to) | Move::MoveCards(1, from, to) => table.move_cards(1, from, to),
+        Move::MoveCards(..) => Err(Box::new(Error::TooManyCards)),
+        _ => Err(Box::new(Error::InvalidMove)),
+    }
+}
+
+/// Lanes are keyed by their number, with 0 standing for lane 10.
+pub fn lane_key(c: char) -> Option<PileType> {
+    match c.to_digit(10)? {
+        0 => Some(PileType::Lane(10)),
+        n => Some(PileType::Lane(n as usize)),
+    }
+}
+
+/// Suit piles are keyed by the initial of their suit.
+pub fn suit_key(c: char) -> Option<PileType> {
+    match c {
+        'C' => Some(PileType::Suit(Suit::Clubs)),
+        'D' => Some(PileType::Suit(Suit::Diamonds)),
+        'H' => Some(PileType::Suit(Suit::Hearts)),
+        'S' => Some(PileType::Suit(Suit::Spades)),
+        _ => None,
+    }
+}
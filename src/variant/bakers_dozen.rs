@@ -0,0 +1,72 @@
+use crate::{
+    core::{
+        Result,
+        config::{Build, EmptyLane, GameConfig},
+        moves::Move,
+        table::Table,
+    },
+    deck::{Deck, card::Rank},
+    pile::PileType,
+    variant::{Variant, lane_key, play_single_cards, suit_key},
+};
+
+const LANES: usize = 13;
+const LANE_CARDS: u8 = 4;
+
+/// Baker's Dozen: thirteen lanes of four face-up cards with the kings moved
+/// to the bottom. Lanes build down regardless of suit, one card at a time,
+/// and empty lanes stay empty.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BakersDozen;
+
+impl Variant for BakersDozen {
+    fn name(&self) -> &str {
+        "Baker's Dozen"
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+            .to_builder()
+            .lanes(LANES)
+            .build_rule(Build::AnySuit)
+            .empty_lane(EmptyLane::None)
+            .build()
+    }
+
+    fn deal(&self, mut deck: Deck, config: GameConfig) -> Table {
+        let lanes = (0..LANES)
+            .map(|_| {
+                let mut cards = deck.pick_cards(LANE_CARDS);
+                cards.iter_mut().for_each(|card| card.flip());
+                cards.sort_by_key(|card| card.rank() != Rank::King);
+                cards
+            })
+            .collect();
+        Table::from_lanes(lanes, vec![], config)
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        play_single_cards(table, game_move)
+    }
+
+    /// Lanes 11 to 13 are keyed by E, F and G, which are free in this variant
+    /// and not taken by the input handler.
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        match c {
+            'E' => Some(PileType::Lane(11)),
+            'F' => Some(PileType::Lane(12)),
+            'G' => Some(PileType::Lane(13)),
+            c => suit_key(c).or(lane_key(c)),
+        }
+    }
+
+    fn pile_keys(&self) -> &str {
+        "Pile can be any among the lane numbers (0 for lane 10, E, F, G for lanes\n    \
+         11 to 13), C, D, H, S"
+    }
+
+    fn print_table(&self, table: &Table) {
+        crate::print_cells_and_suit_piles(table);
+        crate::print_lanes(table);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    core::{
+        Result,
+        config::{Build, EmptyLane, GameConfig},
+        moves::Move,
+        table::Table,
+    },
+    deck::{Deck, card::Rank},
+    pile::PileType,
+    variant::{Variant, lane_key, play_single_cards, suit_key},
+};
+
+const LANES: usize = 8;
+
+/// Beleaguered Castle: the aces start on the suit piles and the other cards
+/// are dealt face up into eight lanes, which build down regardless of suit,
+/// one card at a time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BeleagueredCastle;
+
+impl Variant for BeleagueredCastle {
+    fn name(&self) -> &str {
+        "Beleaguered Castle"
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+            .to_builder()
+            .lanes(LANES)
+            .build_rule(Build::AnySuit)
+            .empty_lane(EmptyLane::Any)
+            .build()
+    }
+
+    fn deal(&self, deck: Deck, config: GameConfig) -> Table {
+        let (aces, mut cards): (Vec<_>, Vec<_>) = deck
+            .pick_all_cards()
+            .into_iter()
+            .partition(|card| card.rank() == Rank::Ace);
+        cards.iter_mut().for_each(|card| card.flip());
+        let lanes = cards
+            .chunks(cards.len() / LANES)
+            .map(|lane| lane.to_vec())
+            .collect();
+        let table = Table::from_lanes(lanes, vec![], config);
+        for ace in aces {
+            table
+                .deal_to_foundation(ace)
+                .expect("every ace has an empty suit pile");
+        }
+        table
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        play_single_cards(table, game_move)
+    }
+
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        suit_key(c).or(lane_key(c))
+    }
+
+    fn pile_keys(&self) -> &str {
+        "Pile can be any among the lane numbers, C, D, H, S"
+    }
+
+    fn print_table(&self, table: &Table) {
+        crate::print_cells_and_suit_piles(table);
+        crate::print_lanes(table);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    core::{
+        Error, Result,
+        config::{Build, EmptyLane, GameConfig},
+        moves::Move,
+        table::Table,
+    },
+    deck::Deck,
+    pile::PileType,
+    variant::{Variant, lane_key, suit_key},
+};
+
+const LANES: usize = 4;
+const RESERVE_CARDS: u8 = 13;
+
+/// Canfield: a reserve of thirteen cards, four lanes building down in
+/// alternate colours and suit piles starting from the rank of the first card
+/// dealt to them. Ranks wrap around from king to ace, and empty lanes are
+/// filled from the reserve.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Canfield;
+
+impl Canfield {
+    /// Moves the top card of the reserve onto each empty lane.
+    fn fill_empty_lanes(table: &Table) -> Result<()> {
+        for i in 1..=table.lane_count() {
+            if table.lane(i - 1).is_empty() && !table.reserve().is_empty() {
+                table.move_cards(1, PileType::Reserve, PileType::Lane(i))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Variant for Canfield {
+    fn name(&self) -> &str {
+        "Canfield"
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+            .to_builder()
+            .lanes(LANES)
+            .build_rule(Build::AlternateColours)
+            .empty_lane(EmptyLane::Any)
+            .wrap(true)
+            .build()
+    }
+
+    /// Deals the reserve, then the first card of the suit piles, which sets
+    /// their base rank, then one card on each lane.
+    fn deal(&self, mut deck: Deck, config: GameConfig) -> Table {
+        let reserve = deck.pick_cards(RESERVE_CARDS);
+        let base = deck.pick_cards(1)[0];
+        let config = config.to_builder().base_rank(base.rank()).build();
+        let lanes = (0..LANES).map(|_| deck.pick_cards(1)).collect();
+        let table = Table::from_lanes(lanes, deck.pick_all_cards(), config).with_reserve(reserve);
+        table
+            .deal_to_foundation(base)
+            .expect("the base card fits on its empty suit pile");
+        table
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        match game_move {
+            Move::DrawCard => table.draw_card()?,
+            Move::AutoMove(from, to) => table.auto_move(from, to)?,
+            Move::MoveCards(n, from, to) => table.move_cards(n, from, to)?,
+            _ => return Err(Box::new(Error::InvalidMove)),
+        }
+        Canfield::fill_empty_lanes(table)
+    }
+
+    /// Unlike Klondike, lanes can run down across the base rank of the suit
+    /// piles and block each other, so the finish is played out on a copy of
+    /// the table to make sure every card reaches the suit piles.
+    fn can_auto_finish(&self, table: &Table) -> bool {
+        if !table.reserve().is_empty()
+            || !table.draw_pile().is_empty()
+            || !table.uncovered_pile().is_empty()
+        {
+            return false;
+        }
+        let table = table.clone();
+        loop {
+            let moved = (1..=table.lane_count()).any(|i| {
+                let suit = table.lane(i - 1).top_card().map(|card| card.suit());
+                suit.is_some_and(|suit| {
+                    table
+                        .move_cards(1, PileType::Lane(i), PileType::Suit(suit))
+                        .is_ok()
+                })
+            });
+            if !moved {
+                return table.suit_cards() == 52;
+            }
+        }
+    }
+
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        match c {
+            'P' => Some(PileType::Uncovered),
+            'R' => Some(PileType::Reserve),
+            c => suit_key(c).or(lane_key(c)),
+        }
+    }
+
+    fn pile_keys(&self) -> &str {
+        "Pile can be any among the lane numbers, P, R, C, D, H, S"
+    }
+
+    fn print_table(&self, table: &Table) {
+        println!(" R");
+        println!("{}\t{} left\n", table.reserve(), table.reserve().length());
+        crate::print_table(table);
+    }
+}
@@ -0,0 +1,71 @@
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::{
+        Result,
+        config::{Build, EmptyLane, GameConfig},
+        moves::Move,
+        table::Table,
+    },
+    deck::{Deck, card::Suit},
+    variant::{Variant, play_single_cards},
+};
+
+const DECKS: usize = 2;
+const LANES: usize = 10;
+const LANE_CARDS: u8 = 4;
+
+/// Forty Thieves: two decks, ten lanes of four face-up cards building down by
+/// suit, and eight suit piles. Only one card can be moved at a time and the
+/// stock can be gone through once.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FortyThieves;
+
+impl FortyThieves {
+    fn foundations() -> Vec<Suit> {
+        Suit::iter()
+            .flat_map(|suit| std::iter::repeat_n(suit, DECKS))
+            .collect()
+    }
+}
+
+impl Variant for FortyThieves {
+    fn name(&self) -> &str {
+        "Forty Thieves"
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+            .to_builder()
+            .lanes(LANES)
+            .build_rule(Build::SameSuit)
+            .empty_lane(EmptyLane::Any)
+            .draw_count(1)
+            .max_passes(Some(1))
+            .build()
+    }
+
+    fn deck(&self, seed: u64) -> Deck {
+        Deck::with_decks(seed, DECKS)
+    }
+
+    fn deal(&self, mut deck: Deck, config: GameConfig) -> Table {
+        let lanes = (0..LANES)
+            .map(|_| {
+                let mut cards = deck.pick_cards(LANE_CARDS);
+                cards.iter_mut().for_each(|card| card.flip());
+                cards
+            })
+            .collect();
+        Table::from_lanes(lanes, deck.pick_all_cards(), config)
+            .with_foundations(&FortyThieves::foundations())
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        play_single_cards(table, game_move)
+    }
+
+    fn is_won(&self, table: &Table) -> bool {
+        table.suit_cards() == 52 * DECKS
+    }
+}
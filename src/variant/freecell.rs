@@ -0,0 +1,179 @@
+use crate::{
+    core::{
+        Error, Result,
+        config::{Build, EmptyLane, GameConfig},
+        moves::Move,
+        table::Table,
+    },
+    deck::Deck,
+    pile::{CELL_KEYS, PileType},
+    variant::{Variant, lane_key, suit_key},
+};
+
+/// FreeCell: every card is dealt face up into eight cascades, and four free
+/// cells can each hold a single card. The other games of the family change
+/// the layout, the number of cells and how lanes are built.
+#[derive(Debug, Clone, Copy)]
+pub struct FreeCell {
+    name: &'static str,
+    lanes: usize,
+    cells: usize,
+    /// The number of cards dealt to the free cells rather than the lanes.
+    cell_cards: usize,
+    build_rule: Build,
+    empty_lane: EmptyLane,
+}
+
+impl FreeCell {
+    pub fn new() -> FreeCell {
+        FreeCell {
+            name: "FreeCell",
+            lanes: 8,
+            cells: 4,
+            cell_cards: 0,
+            build_rule: Build::AlternateColours,
+            empty_lane: EmptyLane::Any,
+        }
+    }
+
+    /// FreeCell building down by suit.
+    pub fn bakers_game() -> FreeCell {
+        FreeCell {
+            name: "Baker's Game",
+            build_rule: Build::SameSuit,
+            ..FreeCell::new()
+        }
+    }
+
+    /// Ten lanes of five cards building down by suit, with the last two cards
+    /// dealt to the free cells. Empty lanes take kings only.
+    pub fn seahaven_towers() -> FreeCell {
+        FreeCell {
+            name: "Seahaven Towers",
+            lanes: 10,
+            cell_cards: 2,
+            build_rule: Build::SameSuit,
+            empty_lane: EmptyLane::Kings,
+            ..FreeCell::new()
+        }
+    }
+
+    /// Eight lanes of six cards building down by suit and eight free cells,
+    /// four of them dealt a card. Empty lanes take kings only.
+    pub fn eight_off() -> FreeCell {
+        FreeCell {
+            name: "Eight Off",
+            cells: 8,
+            cell_cards: 4,
+            build_rule: Build::SameSuit,
+            empty_lane: EmptyLane::Kings,
+            ..FreeCell::new()
+        }
+    }
+
+    /// The number of cards that can be moved at once by using the free cells
+    /// and the empty lanes as temporary storage. Lanes that take kings only
+    /// cannot hold the cards in between.
+    pub fn supermove_limit(table: &Table, to: PileType) -> usize {
+        if table.config().empty_lane() != EmptyLane::Any {
+            return table.empty_cells() + 1;
+        }
+        let mut empty_lanes = table.empty_lanes();
+        if let PileType::Lane(i) = to
+            && (1..=table.lane_count()).contains(&i)
+            && table.lane(i - 1).is_empty()
+        {
+            empty_lanes -= 1;
+        }
+        (table.empty_cells() + 1) << empty_lanes
+    }
+
+    fn move_cards(table: &Table, number: usize, from: PileType, to: PileType) -> Result<()> {
+        if number > FreeCell::supermove_limit(table, to) {
+            return Err(Box::new(Error::TooManyCards));
+        }
+        table.move_cards(number, from, to)
+    }
+
+    /// Moves the longest run that fits on the destination.
+    fn auto_move(table: &Table, from: PileType, to: PileType) -> Result<()> {
+        let limit = FreeCell::supermove_limit(table, to);
+        let number = (1..=limit)
+            .rev()
+            .find(|&n| table.can_move_cards(n, from, to))
+            .ok_or(Error::InvalidMove)?;
+        table.move_cards(number, from, to)
+    }
+}
+
+impl Default for FreeCell {
+    fn default() -> Self {
+        FreeCell::new()
+    }
+}
+
+impl Variant for FreeCell {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+            .to_builder()
+            .lanes(self.lanes)
+            .build_rule(self.build_rule)
+            .empty_lane(self.empty_lane)
+            .build()
+    }
+
+    /// The cards left after filling the free cells are shared out among the
+    /// lanes, the first lanes getting one more when they do not divide evenly.
+    fn deal(&self, mut deck: Deck, config: GameConfig) -> Table {
+        let lane_cards = deck.len() - self.cell_cards;
+        let lanes = (0..self.lanes)
+            .map(|i| {
+                let extra = usize::from(i < lane_cards % self.lanes);
+                let mut cards = deck.pick_cards((lane_cards / self.lanes + extra) as u8);
+                cards.iter_mut().for_each(|card| card.flip());
+                cards
+            })
+            .collect();
+        let table = Table::from_lanes(lanes, vec![], config).with_cells(self.cells);
+        for card in deck.pick_all_cards() {
+            table
+                .deal_to_cell(card)
+                .expect("there are more free cells than cards dealt to them");
+        }
+        table
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        match game_move {
+            Move::AutoMove(from, to) => FreeCell::auto_move(table, from, to),
+            Move::MoveCards(n, from, to) => FreeCell::move_cards(table, n, from, to),
+            _ => Err(Box::new(Error::InvalidMove)),
+        }
+    }
+
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        match CELL_KEYS[..self.cells].iter().position(|&key| key == c) {
+            Some(i) => Some(PileType::Cell(i + 1)),
+            None => suit_key(c).or(lane_key(c)),
+        }
+    }
+
+    fn pile_keys(&self) -> &str {
+        match self.cells {
+            4 => "Pile can be any among the lane numbers, W, X, Y, Z (free cells), C, D, H, S",
+            _ => {
+                "Pile can be any among the lane numbers, W, X, Y, Z, R, T, V, O (free cells),\n    \
+                 C, D, H, S"
+            }
+        }
+    }
+
+    fn print_table(&self, table: &Table) {
+        crate::print_cells_and_suit_piles(table);
+        crate::print_lanes(table);
+    }
+}
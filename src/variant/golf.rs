@@ -0,0 +1,86 @@
+use crate::{
+    core::{
+        Error, Result,
+        config::{EmptyLane, GameConfig},
+        moves::Move,
+        score::Scoring,
+        table::Table,
+    },
+    deck::Deck,
+    pile::PileType,
+    variant::{Variant, lane_key},
+};
+
+const LANES: usize = 7;
+const LANE_CARDS: u8 = 5;
+
+/// Golf: seven lanes of five face-up cards are cleared onto a single discard
+/// pile, which takes any card one rank above or below its top card. The
+/// score is the number of cards left over.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Golf;
+
+impl Variant for Golf {
+    fn name(&self) -> &str {
+        "Golf"
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+            .to_builder()
+            .lanes(LANES)
+            .empty_lane(EmptyLane::Any)
+            .draw_count(1)
+            .max_passes(Some(1))
+            .scoring(Scoring::CardsLeft)
+            .build()
+    }
+
+    /// The stock turns its first card onto the discard pile.
+    fn deal(&self, mut deck: Deck, config: GameConfig) -> Table {
+        let lanes = (0..LANES)
+            .map(|_| {
+                let mut cards = deck.pick_cards(LANE_CARDS);
+                cards.iter_mut().for_each(|card| card.flip());
+                cards
+            })
+            .collect();
+        let mut table = Table::from_lanes(lanes, deck.pick_all_cards(), config).with_discard();
+        table
+            .draw_card()
+            .expect("the stock holds the cards left after the deal");
+        table
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        match game_move {
+            Move::DrawCard => table.draw_card(),
+            Move::AutoMove(from @ PileType::Lane(_), PileType::Discard)
+            | Move::MoveCards(1, from @ PileType::Lane(_), PileType::Discard) => {
+                table.move_cards(1, from, PileType::Discard)
+            }
+            _ => Err(Box::new(Error::InvalidMove)),
+        }
+    }
+
+    fn is_won(&self, table: &Table) -> bool {
+        (0..table.lane_count()).all(|i| table.lane(i).is_empty())
+    }
+
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        match c {
+            'P' => Some(PileType::Discard),
+            c => lane_key(c),
+        }
+    }
+
+    fn pile_keys(&self) -> &str {
+        "Pile can be any among the lane numbers and P"
+    }
+
+    fn print_table(&self, table: &Table) {
+        println!(" N\t P");
+        println!("{}\t{}\n", table.draw_pile(), table.uncovered_pile());
+        crate::print_lanes(table);
+    }
+}
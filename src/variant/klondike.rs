@@ -0,0 +1,29 @@
+use crate::{
+    core::{config::GameConfig, table::Table},
+    deck::Deck,
+    variant::Variant,
+};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Klondike;
+
+impl Variant for Klondike {
+    fn name(&self) -> &str {
+        "Klondike"
+    }
+
+    /// Lane `n` is dealt `n` cards, so there can be at most nine lanes.
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config.to_builder().lanes(config.lanes().min(9)).build()
+    }
+
+    fn deal(&self, deck: Deck, config: GameConfig) -> Table {
+        Table::from_deck(deck, config)
+    }
+
+    fn can_auto_finish(&self, table: &Table) -> bool {
+        let lanes_uncovered = table.covered_cards() == 0;
+        let piles_empty = table.draw_pile().is_empty() && table.uncovered_pile().is_empty();
+        lanes_uncovered && piles_empty
+    }
+}
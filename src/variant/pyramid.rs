@@ -0,0 +1,143 @@
+use crate::{
+    core::{Error, Result, config::GameConfig, moves::Move, table::Table},
+    deck::{Deck, card::Card},
+    pile::PileType,
+    variant::{Variant, lane_key},
+};
+
+const ROWS: usize = 7;
+const PAIR_SUM: u8 = 13;
+
+/// Pyramid: 28 face-up cards are dealt in seven rows, each card covered by the
+/// two below it. Pairs of available cards adding up to 13 are removed, kings
+/// on their own, until the pyramid is cleared.
+///
+/// Each lane holds a diagonal of the pyramid: lane `n` starts from the `n`-th
+/// card of the bottom row and goes up to the `n`-th row. The top card of a
+/// lane is then covered only by the lane on its right, and is available once
+/// that lane is shorter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pyramid;
+
+impl Pyramid {
+    /// The card that can be removed from a pile, if any.
+    fn available_card(table: &Table, pile: PileType) -> Option<Card> {
+        match pile {
+            PileType::Uncovered => table.uncovered_pile().top_card().copied(),
+            PileType::Lane(i) if (1..=ROWS).contains(&i) => {
+                let lane = table.lane(i - 1);
+                let covered = i < ROWS && table.lane(i).length() >= lane.length();
+                lane.top_card().filter(|_| !covered).copied()
+            }
+            _ => None,
+        }
+    }
+
+    fn remove_pair(table: &Table, a: PileType, b: PileType) -> Result<()> {
+        let first = Pyramid::available_card(table, a).ok_or(Error::InvalidMove)?;
+        if a == b {
+            if first.rank() as u8 != PAIR_SUM {
+                return Err(Box::new(Error::InvalidMove));
+            }
+            return table.remove_cards(1, a);
+        }
+        let second = Pyramid::available_card(table, b).ok_or(Error::InvalidMove)?;
+        if first.rank() as u8 + second.rank() as u8 != PAIR_SUM {
+            return Err(Box::new(Error::InvalidMove));
+        }
+        table.remove_cards(1, a)?;
+        table.remove_cards(1, b)
+    }
+}
+
+impl Variant for Pyramid {
+    fn name(&self) -> &str {
+        "Pyramid"
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config.to_builder().lanes(ROWS).draw_count(1).build()
+    }
+
+    /// Deals the pyramid row by row, so that the `n`-th card of each row goes
+    /// to lane `n`.
+    fn deal(&self, mut deck: Deck, config: GameConfig) -> Table {
+        let mut lanes = vec![vec![]; ROWS];
+        for row in 1..=ROWS {
+            for (lane, mut card) in deck.pick_cards(row as u8).into_iter().enumerate() {
+                card.flip();
+                lanes[lane].push(card);
+            }
+        }
+        Table::from_lanes(lanes, deck.pick_all_cards(), config)
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        match game_move {
+            Move::DrawCard => table.draw_card(),
+            Move::RemovePair(a, b) => Pyramid::remove_pair(table, a, b),
+            _ => Err(Box::new(Error::InvalidMove)),
+        }
+    }
+
+    fn legal_moves(&self, table: &Table) -> Vec<Move> {
+        let piles = (1..=ROWS)
+            .map(PileType::Lane)
+            .chain([PileType::Uncovered])
+            .collect::<Vec<PileType>>();
+        let pairs = piles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &a)| piles[i..].iter().map(move |&b| Move::RemovePair(a, b)));
+        [Move::DrawCard]
+            .into_iter()
+            .chain(pairs)
+            .filter(|&game_move| self.play(&mut table.clone(), game_move).is_ok())
+            .collect()
+    }
+
+    fn is_won(&self, table: &Table) -> bool {
+        (0..ROWS).all(|i| table.lane(i).is_empty())
+    }
+
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        match c {
+            'P' => Some(PileType::Uncovered),
+            c => lane_key(c),
+        }
+    }
+
+    fn pile_move(&self, a: PileType, b: PileType) -> Move {
+        Move::RemovePair(a, b)
+    }
+
+    fn pile_keys(&self) -> &str {
+        "Pile can be any among the lane numbers and P. Name two piles to remove\n    \
+         their cards, or the same pile twice to remove a king"
+    }
+
+    /// Prints the stock, the waste and the available cards above their keys,
+    /// followed by the pyramid itself.
+    fn print_table(&self, table: &Table) {
+        let keys = (1..=ROWS).map(|i| format!(" {}\t", i)).collect::<String>();
+        println!(" N\t P\t\t{}", keys);
+        print!("{}\t{}\t\t", table.draw_pile(), table.uncovered_pile());
+        for i in 1..=ROWS {
+            match Pyramid::available_card(table, PileType::Lane(i)) {
+                Some(card) => print!("{}\t", card),
+                None => print!("\t"),
+            }
+        }
+        println!("\n");
+        for row in 0..ROWS {
+            print!("{}", "  ".repeat(ROWS - row - 1));
+            for lane in 0..=row {
+                match table.lane(lane).card(row - lane) {
+                    Some(card) => print!("{} ", card),
+                    None => print!("    "),
+                }
+            }
+            println!();
+        }
+    }
+}
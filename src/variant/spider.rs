@@ -0,0 +1,182 @@
+use crate::{
+    core::{
+        Error, Result,
+        config::{Build, EmptyLane, GameConfig},
+        moves::Move,
+        table::Table,
+    },
+    deck::{
+        Deck,
+        card::{Rank, Suit},
+    },
+    pile::PileType,
+    variant::{Variant, lane_key},
+};
+
+const LANES: usize = 10;
+const RUN_LENGTH: usize = 13;
+
+/// Spider with two decks of one, two or four suits. Lanes build down
+/// regardless of suit, but only runs of the same suit can be moved together,
+/// and complete runs from king to ace are removed to the suit piles.
+#[derive(Debug, Clone, Copy)]
+pub struct Spider {
+    suits: usize,
+}
+
+impl Spider {
+    /// `suits` is rounded to one, two or four.
+    pub fn new(suits: usize) -> Spider {
+        let suits = match suits {
+            0 | 1 => 1,
+            2 | 3 => 2,
+            _ => 4,
+        };
+        Spider { suits }
+    }
+
+    pub fn suits(&self) -> usize {
+        self.suits
+    }
+
+    /// The number of cards on top of the lane that form a same-suit run, the
+    /// largest that can be moved together.
+    fn run_length(table: &Table, lane: usize) -> usize {
+        let cards = table.lane(lane - 1).cards();
+        let run = cards
+            .iter()
+            .rev()
+            .zip(cards.iter().rev().skip(1))
+            .take_while(|(top, below)| {
+                !below.is_covered() && below.suit() == top.suit() && below.is_next(top)
+            })
+            .count();
+        match cards.last() {
+            Some(card) if !card.is_covered() => run + 1,
+            _ => 0,
+        }
+    }
+
+    /// Fails unless both lanes, numbered from 1, are on the table.
+    fn check_lanes(table: &Table, from: usize, to: usize) -> Result<()> {
+        let lanes = 1..=table.lane_count();
+        if !lanes.contains(&from) || !lanes.contains(&to) {
+            return Err(Box::new(Error::InvalidMove));
+        }
+        Ok(())
+    }
+
+    fn move_run(table: &Table, number: usize, from: usize, to: usize) -> Result<()> {
+        Spider::check_lanes(table, from, to)?;
+        if number > Spider::run_length(table, from) {
+            return Err(Box::new(Error::InvalidMove));
+        }
+        table.move_cards(number, PileType::Lane(from), PileType::Lane(to))
+    }
+
+    fn auto_move(table: &Table, from: usize, to: usize) -> Result<()> {
+        Spider::check_lanes(table, from, to)?;
+        let run = Spider::run_length(table, from);
+        let cards = table.lane(from - 1).cards();
+        let number = (1..=run)
+            .rev()
+            .find(|n| {
+                let card = &cards[cards.len() - n];
+                table.lane(to - 1).can_add(card, table.config())
+            })
+            .ok_or(Error::InvalidMove)?;
+        table.move_cards(number, PileType::Lane(from), PileType::Lane(to))
+    }
+
+    fn remove_complete_runs(table: &Table) -> Result<()> {
+        for i in 0..table.lane_count() {
+            let top = Spider::run_length(table, i + 1);
+            let lane = table.lane(i);
+            let complete = lane
+                .length()
+                .checked_sub(RUN_LENGTH)
+                .and_then(|i| lane.card(i))
+                .is_some_and(|card| top >= RUN_LENGTH && card.rank() == Rank::King);
+            drop(lane);
+            if complete {
+                table.remove_cards(RUN_LENGTH, PileType::Lane(i + 1))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The suit of each of the eight runs.
+    fn run_suits(&self) -> Vec<Suit> {
+        let suits = match self.suits {
+            1 => vec![Suit::Spades],
+            2 => vec![Suit::Spades, Suit::Hearts],
+            _ => vec![Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades],
+        };
+        suits.into_iter().cycle().take(8).collect()
+    }
+}
+
+impl Default for Spider {
+    fn default() -> Self {
+        Spider::new(1)
+    }
+}
+
+impl Variant for Spider {
+    fn name(&self) -> &str {
+        match self.suits {
+            1 => "Spider (1 suit)",
+            2 => "Spider (2 suits)",
+            _ => "Spider (4 suits)",
+        }
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+            .to_builder()
+            .lanes(LANES)
+            .build_rule(Build::AnySuit)
+            .empty_lane(EmptyLane::Any)
+            .draw_count(1)
+            .max_passes(None)
+            .build()
+    }
+
+    fn deck(&self, seed: u64) -> Deck {
+        Deck::with_suits(seed, &self.run_suits())
+    }
+
+    /// The first four lanes get six cards, the others five.
+    fn deal(&self, mut deck: Deck, config: GameConfig) -> Table {
+        let lanes = (0..LANES)
+            .map(|i| deck.pick_cards(if i < 4 { 6 } else { 5 }))
+            .collect();
+        Table::from_lanes(lanes, deck.pick_all_cards(), config).with_foundations(&self.run_suits())
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        match game_move {
+            Move::DrawCard => table.deal_to_lanes()?,
+            Move::AutoMove(PileType::Lane(from), PileType::Lane(to)) => {
+                Spider::auto_move(table, from, to)?
+            }
+            Move::MoveCards(n, PileType::Lane(from), PileType::Lane(to)) => {
+                Spider::move_run(table, n, from, to)?
+            }
+            _ => return Err(Box::new(Error::InvalidMove)),
+        }
+        Spider::remove_complete_runs(table)
+    }
+
+    fn is_won(&self, table: &Table) -> bool {
+        table.suit_cards() == 8 * RUN_LENGTH
+    }
+
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        lane_key(c)
+    }
+
+    fn pile_keys(&self) -> &str {
+        "Pile can be any among the lane numbers (0 for lane 10)"
+    }
+}
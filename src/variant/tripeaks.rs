@@ -0,0 +1,172 @@
+use crate::{
+    core::{Error, Result, config::GameConfig, moves::Move, score::Scoring, table::Table},
+    deck::{Deck, card::Card},
+    pile::PileType,
+    variant::{Variant, lane_key},
+};
+
+const LANES: usize = 10;
+const TOP_ROW: usize = 3;
+/// The number of cards dealt to each lane.
+const LANE_CARDS: [usize; LANES] = [4, 3, 2, 4, 3, 2, 4, 3, 2, 1];
+const CARD_WIDTH: usize = 4;
+
+/// TriPeaks: three peaks of cards, each covered by the two below it, are
+/// played onto a discard pile one rank above or below its top card, with
+/// kings and aces adjacent.
+///
+/// Each lane holds a chain of cards going up from a card of the bottom row,
+/// so that the top card of a lane is covered only by the lane on its right,
+/// and is available once that lane's top card is no higher in the peaks.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TriPeaks;
+
+impl TriPeaks {
+    /// The row of the top card of a lane, 0 being the tips of the peaks.
+    fn top_row(table: &Table, lane: usize) -> Option<usize> {
+        let length = table.lane(lane).length();
+        (length > 0).then(|| TOP_ROW + length - LANE_CARDS[lane])
+    }
+
+    /// The card that can be played from a lane, if any.
+    fn available_card(table: &Table, lane: usize) -> Option<Card> {
+        let row = TriPeaks::top_row(table, lane)?;
+        let covered =
+            lane + 1 < LANES && TriPeaks::top_row(table, lane + 1).is_some_and(|next| next > row);
+        match covered {
+            true => None,
+            false => table.lane(lane).top_card().copied(),
+        }
+    }
+
+    /// Turns the available cards face up and the covered ones face down.
+    fn turn_cards(table: &Table) {
+        for i in 0..LANES {
+            let available = TriPeaks::available_card(table, i).is_some();
+            let mut lane = table.lane_mut(i);
+            if lane
+                .top_card()
+                .is_some_and(|card| card.is_covered() == available)
+            {
+                lane.flip_top_card();
+            }
+        }
+    }
+
+    fn play_card(table: &Table, from: PileType) -> Result<()> {
+        match from {
+            PileType::Lane(i)
+                if (1..=LANES).contains(&i) && TriPeaks::available_card(table, i - 1).is_some() =>
+            {
+                table.move_cards(1, from, PileType::Discard)
+            }
+            _ => Err(Box::new(Error::InvalidMove)),
+        }
+    }
+
+    /// Prints cards at the given columns, in order.
+    fn print_row(cards: &[(usize, String)]) {
+        let mut column = 0;
+        for (at, card) in cards {
+            print!("{}{}", " ".repeat(at - column), card);
+            column = at + CARD_WIDTH - 1;
+        }
+        println!();
+    }
+}
+
+impl Variant for TriPeaks {
+    fn name(&self) -> &str {
+        "TriPeaks"
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+            .to_builder()
+            .lanes(LANES)
+            .draw_count(1)
+            .max_passes(Some(1))
+            .wrap(true)
+            .scoring(Scoring::Streak)
+            .build()
+    }
+
+    /// Deals the peaks from their tips down, the bottom row face up, and turns
+    /// the first card of the stock onto the discard pile.
+    fn deal(&self, mut deck: Deck, config: GameConfig) -> Table {
+        let mut lanes = vec![vec![]; LANES];
+        for peak in 0..3 {
+            lanes[3 * peak].append(&mut deck.pick_cards(1));
+        }
+        for (i, card) in deck.pick_cards(6).into_iter().enumerate() {
+            lanes[3 * (i / 2) + i % 2].push(card);
+        }
+        for (i, card) in deck.pick_cards(9).into_iter().enumerate() {
+            lanes[i].push(card);
+        }
+        for (i, card) in deck.pick_cards(10).into_iter().enumerate() {
+            lanes[i].push(card);
+        }
+        let mut table = Table::from_lanes(lanes, deck.pick_all_cards(), config).with_discard();
+        table
+            .draw_card()
+            .expect("the stock holds the cards left after the deal");
+        table
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        match game_move {
+            Move::DrawCard => table.draw_card()?,
+            Move::AutoMove(from, PileType::Discard)
+            | Move::MoveCards(1, from, PileType::Discard) => TriPeaks::play_card(table, from)?,
+            _ => return Err(Box::new(Error::InvalidMove)),
+        }
+        TriPeaks::turn_cards(table);
+        Ok(())
+    }
+
+    fn is_won(&self, table: &Table) -> bool {
+        (0..LANES).all(|i| table.lane(i).is_empty())
+    }
+
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        match c {
+            'P' => Some(PileType::Discard),
+            c => lane_key(c),
+        }
+    }
+
+    fn pile_keys(&self) -> &str {
+        "Pile can be any among the lane numbers (0 for lane 10) and P"
+    }
+
+    /// Prints the stock and the discard pile, then the peaks with the
+    /// available cards and the keys of their lanes underneath.
+    fn print_table(&self, table: &Table) {
+        println!(" N\t P");
+        println!("{}\t{}\n", table.draw_pile(), table.uncovered_pile());
+        for row in 0..=TOP_ROW {
+            let cards = (0..LANES)
+                .filter_map(|lane| {
+                    let index = row.checked_sub(TOP_ROW + 1 - LANE_CARDS[lane])?;
+                    let card = table.lane(lane).card(index)?.to_string();
+                    // Each card sits halfway between the two cards covering it.
+                    Some((lane * CARD_WIDTH + (TOP_ROW - row) * CARD_WIDTH / 2, card))
+                })
+                .collect::<Vec<(usize, String)>>();
+            TriPeaks::print_row(&cards);
+        }
+        println!();
+        let available = (0..LANES)
+            .filter_map(|lane| {
+                TriPeaks::available_card(table, lane)
+                    .map(|card| (lane * CARD_WIDTH, card.to_string()))
+            })
+            .collect::<Vec<(usize, String)>>();
+        TriPeaks::print_row(&available);
+        let keys = (1..=LANES)
+            .map(|i| ((i - 1) * CARD_WIDTH, format!(" {} ", i % 10)))
+            .collect::<Vec<(usize, String)>>();
+        TriPeaks::print_row(&keys);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    core::{
+        Error, Result,
+        config::{Build, EmptyLane, GameConfig},
+        moves::Move,
+        table::Table,
+    },
+    deck::Deck,
+    pile::PileType,
+    variant::{Variant, lane_key, suit_key},
+};
+
+const LANES: usize = 7;
+const FACE_UP: usize = 5;
+
+/// Yukon: Klondike's layout without a stock, where any face-up card can be
+/// moved together with the cards on top of it. Russian Solitaire plays the
+/// same way but builds down by suit.
+#[derive(Debug, Clone, Copy)]
+pub struct Yukon {
+    build_rule: Build,
+}
+
+impl Yukon {
+    pub fn new() -> Yukon {
+        Yukon {
+            build_rule: Build::AlternateColours,
+        }
+    }
+
+    pub fn russian() -> Yukon {
+        Yukon {
+            build_rule: Build::SameSuit,
+        }
+    }
+
+    /// Moves the smallest group whose bottom card fits on the destination.
+    fn auto_move(table: &Table, from: PileType, to: PileType) -> Result<()> {
+        let length = match (from, to) {
+            (PileType::Lane(i), PileType::Lane(_)) if (1..=table.lane_count()).contains(&i) => {
+                table.lane(i - 1).length()
+            }
+            _ => return table.move_cards(1, from, to),
+        };
+        let number = (1..=length)
+            .find(|&n| table.can_move_group(n, from, to))
+            .ok_or(Error::InvalidMove)?;
+        table.move_group(number, from, to)
+    }
+}
+
+impl Default for Yukon {
+    fn default() -> Self {
+        Yukon::new()
+    }
+}
+
+impl Variant for Yukon {
+    fn name(&self) -> &str {
+        match self.build_rule {
+            Build::SameSuit => "Russian Solitaire",
+            _ => "Yukon",
+        }
+    }
+
+    fn config(&self, config: GameConfig) -> GameConfig {
+        config
+            .to_builder()
+            .lanes(LANES)
+            .build_rule(self.build_rule)
+            .empty_lane(EmptyLane::Kings)
+            .build()
+    }
+
+    /// The first lane gets a single card, lane `n` gets `n - 1` face-down
+    /// cards with five face-up cards on top.
+    fn deal(&self, mut deck: Deck, config: GameConfig) -> Table {
+        let lanes = (0..LANES as u8)
+            .map(|i| {
+                let mut cards = deck.pick_cards(i);
+                let face_up = if i == 0 { 1 } else { FACE_UP as u8 };
+                let mut up = deck.pick_cards(face_up);
+                up.iter_mut().for_each(|card| card.flip());
+                cards.append(&mut up);
+                cards
+            })
+            .collect();
+        Table::from_lanes(lanes, vec![], config)
+    }
+
+    fn play(&self, table: &mut Table, game_move: Move) -> Result<()> {
+        match game_move {
+            Move::AutoMove(from, to) => Yukon::auto_move(table, from, to),
+            Move::MoveCards(n, from @ PileType::Lane(_), to @ PileType::Lane(_)) => {
+                table.move_group(n, from, to)
+            }
+            Move::MoveCards(n, PileType::Suit(_), _) if n > 1 => Err(Box::new(Error::InvalidMove)),
+            Move::MoveCards(n, from, to) => table.move_cards(n, from, to),
+            _ => Err(Box::new(Error::InvalidMove)),
+        }
+    }
+
+    /// The table only lists groups that are built down, while any face-up
+    /// group can be moved from one lane onto another here.
+    fn legal_moves(&self, table: &Table) -> Vec<Move> {
+        let mut moves = table
+            .legal_moves()
+            .into_iter()
+            .filter(|game_move| {
+                !matches!(
+                    game_move,
+                    Move::MoveCards(_, PileType::Lane(_), PileType::Lane(_))
+                )
+            })
+            .collect::<Vec<Move>>();
+        let lanes = (1..=table.lane_count()).map(PileType::Lane);
+        for (i, from) in lanes.clone().enumerate() {
+            let length = table.lane(i).length();
+            for to in lanes.clone() {
+                let numbers = (1..=length).filter(|&n| table.can_move_group(n, from, to));
+                moves.extend(numbers.map(|n| Move::MoveCards(n, from, to)));
+            }
+        }
+        moves
+    }
+
+    fn parse_pile(&self, c: char) -> Option<PileType> {
+        suit_key(c).or(lane_key(c))
+    }
+
+    fn pile_keys(&self) -> &str {
+        "Pile can be any among the lane numbers, C, D, H, S"
+    }
+
+    fn print_table(&self, table: &Table) {
+        crate::print_cells_and_suit_piles(table);
+        crate::print_lanes(table);
+    }
+}